@@ -22,4 +22,163 @@ pub fn assert_dst_op(res: &mut [u8], lhs: &[u8], rhs: &[u8]) {
         "Res must be bigger than or equal in size to lhs"
     );
 }
-*/
\ No newline at end of file
+*/
+
+use crate::num::{BigNum, BigNumBits, BigNumBitsMut, DynamicBigNum, UnsignedNumDigit, VecBigNum};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+
+// Helpers below operate on normalized `VecBigNum`s and are shared by number types built on top of them.
+
+/// Shifts number left by `n` bits, growing it when needed.
+pub(crate) fn shift_left<D>(num: &mut VecBigNum<D>, n: u64)
+where
+    D: UnsignedNumDigit,
+{
+    SimpleEngine::shift_left_resize(num, n);
+}
+
+/// Shifts number right by `n` bits, dropping bits shifted out.
+pub(crate) fn shift_right<D>(num: &mut VecBigNum<D>, n: u64)
+where
+    D: UnsignedNumDigit,
+{
+    SimpleEngine::shift_right_u64(num, n);
+    num.normalize();
+}
+
+pub(crate) fn mul<D>(lhs: &VecBigNum<D>, rhs: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = VecBigNum::new_zeroed();
+    SimpleEngine::mul_resize(&mut res, lhs, rhs);
+    res.normalize();
+    res
+}
+
+/// Returns quotient and remainder of division.
+pub(crate) fn div_rem<D>(lhs: &VecBigNum<D>, rhs: &VecBigNum<D>) -> (VecBigNum<D>, VecBigNum<D>)
+where
+    D: UnsignedNumDigit,
+{
+    let mut quot = VecBigNum::new_zeroed();
+    let mut rem = VecBigNum::new_zeroed();
+    SimpleEngine::div_rem_resize(&mut quot, &mut rem, lhs, rhs);
+    quot.normalize();
+    rem.normalize();
+    (quot, rem)
+}
+
+pub(crate) fn div<D>(lhs: &VecBigNum<D>, rhs: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    div_rem(lhs, rhs).0
+}
+
+pub(crate) fn pow_u64<D>(base: &VecBigNum<D>, exponent: u64) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = from_u64(1);
+    for i in (0..64 - exponent.leading_zeros()).rev() {
+        res = mul(&res, &res);
+        if exponent >> i & 1 == 1 {
+            res = mul(&res, base);
+        }
+    }
+    res
+}
+
+/// Creates number with value of primitive integer, for any digit size.
+pub(crate) fn from_u64<D>(v: u64) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res =
+        VecBigNum::new_zeroed_sized((64 + D::NUM_BITS as usize - 1) / D::NUM_BITS as usize);
+    for bit in 0..64 {
+        if (v >> bit) & 1 == 1 {
+            res.set_bit(bit);
+        }
+    }
+    res.normalize();
+    res
+}
+
+/// Returns value of number.
+///
+/// # Panic
+/// Panics when number does not fit `u64`.
+pub(crate) fn to_u64<D>(num: &VecBigNum<D>) -> u64
+where
+    D: UnsignedNumDigit,
+{
+    assert!(num.bit_len() <= 64, "Number does not fit in u64");
+    (0..num.bit_len())
+        .filter(|&i| num.test_bit(i))
+        .fold(0, |acc, i| acc | 1 << i)
+}
+
+/// Simple xorshift generator, so tests do not depend on external crates.
+#[cfg(test)]
+pub(crate) fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Implements operator and its assigning version for owned values and references on the right hand side,
+/// forwarding them to the implementation for two references, which has to be written by hand.
+///
+/// Generic parameters of the type are given in brackets and must include digit type `D`.
+macro_rules! forward_ref_op {
+    ([$($gen:tt)*] $type:ty, $trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident) => {
+        impl<$($gen)*> $trait for $type
+        where
+            D: UnsignedNumDigit,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self {
+                $trait::$fn(&self, &rhs)
+            }
+        }
+
+        impl<'r, $($gen)*> $trait<&'r $type> for $type
+        where
+            D: UnsignedNumDigit,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: &'r Self) -> Self {
+                $trait::$fn(&self, rhs)
+            }
+        }
+
+        impl<$($gen)*> $assign_trait for $type
+        where
+            D: UnsignedNumDigit,
+        {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $trait::$fn(&*self, &rhs);
+            }
+        }
+
+        impl<'r, $($gen)*> $assign_trait<&'r $type> for $type
+        where
+            D: UnsignedNumDigit,
+        {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: &'r Self) {
+                *self = $trait::$fn(&*self, rhs);
+            }
+        }
+    };
+}
+
+pub(crate) use forward_ref_op;
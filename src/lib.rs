@@ -2,3 +2,5 @@ pub(crate) mod iutil;
 pub mod ops;
 
 pub mod num;
+
+pub mod rsa;
@@ -0,0 +1,286 @@
+use crate::num::{BigNum, BigNumMut, UnsignedNumDigit};
+use core::ops::Range;
+
+/// Returns `D::NUM_BITS` bits of `num` starting at bit `pos`.
+/// Bits above the most significant digit are zeros.
+fn digit_at_bit<N>(num: &N, pos: usize) -> N::Digit
+where
+    N: BigNum,
+{
+    let bits = N::Digit::NUM_BITS as usize;
+    let digit = pos / bits;
+    let shift = (pos % bits) as u32;
+
+    let get = |i: usize| {
+        if i < num.len() {
+            num[i]
+        } else {
+            N::Digit::ZERO
+        }
+    };
+
+    if shift == 0 {
+        get(digit)
+    } else {
+        (get(digit) >> shift) | (get(digit + 1) << (N::Digit::NUM_BITS - shift))
+    }
+}
+
+/// Bit level inspection of bignums.
+/// Bits are numbered from the least significant one, which has position zero.
+pub trait BigNumBits: BigNum {
+    /// Returns amount of bits required to store value of this number.
+    /// It's zero for zero.
+    fn bit_len(&self) -> usize;
+
+    /// Returns amount of zero bits above the most significant set bit.
+    /// Number is treated as having `len() * NUM_BITS` bits.
+    fn leading_zeros(&self) -> usize;
+
+    /// Returns amount of zero bits below the least significant set bit.
+    /// For zero it's `len() * NUM_BITS`, just like for primitive integers.
+    fn trailing_zeros(&self) -> usize;
+
+    /// Returns amount of set bits.
+    fn count_ones(&self) -> usize;
+
+    /// Returns true if bit at position `pos` is set.
+    /// Bits above the most significant digit are never set.
+    fn test_bit(&self, pos: usize) -> bool;
+
+    /// Returns number, which contains bits of this number from given range shifted to the position zero.
+    /// Result has the same amount of digits as this number, so range must not be longer than it.
+    /// Bits above the most significant digit are zeros.
+    ///
+    /// # Panic
+    /// Panics when range has more bits than this number has.
+    fn extract_bits(&self, range: Range<usize>) -> Self
+    where
+        Self: BigNumMut;
+
+    /// Returns amount of bits, which differ between this number and `other`.
+    /// Shorter number is treated as if it was extended with zero digits.
+    fn hamming_distance(&self, other: &impl BigNum<Digit = Self::Digit>) -> usize;
+}
+
+/// Bit level manipulation of bignums.
+/// Bits are numbered from the least significant one, which has position zero.
+pub trait BigNumBitsMut: BigNumMut {
+    /// Sets bit at position `pos`.
+    ///
+    /// # Panic
+    /// Panics when position is outside of number.
+    fn set_bit(&mut self, pos: usize);
+
+    /// Clears bit at position `pos`.
+    /// Clearing bit outside of number is noop, since it's never set.
+    fn clear_bit(&mut self, pos: usize);
+
+    /// Negates bit at position `pos`.
+    ///
+    /// # Panic
+    /// Panics when position is outside of number.
+    fn flip_bit(&mut self, pos: usize);
+}
+
+impl<T> BigNumBits for T
+where
+    T: BigNum,
+{
+    fn bit_len(&self) -> usize {
+        self.len() * T::Digit::NUM_BITS as usize - BigNumBits::leading_zeros(self)
+    }
+
+    fn leading_zeros(&self) -> usize {
+        let mut res = 0;
+        for i in (0..self.len()).rev() {
+            let zeros = self[i].leading_zeros();
+            res += zeros as usize;
+            if zeros != T::Digit::NUM_BITS {
+                break;
+            }
+        }
+        res
+    }
+
+    fn trailing_zeros(&self) -> usize {
+        let mut res = 0;
+        for i in 0..self.len() {
+            let zeros = self[i].trailing_zeros();
+            res += zeros as usize;
+            if zeros != T::Digit::NUM_BITS {
+                break;
+            }
+        }
+        res
+    }
+
+    fn count_ones(&self) -> usize {
+        (0..self.len()).map(|i| self[i].count_ones() as usize).sum()
+    }
+
+    fn test_bit(&self, pos: usize) -> bool {
+        let bits = T::Digit::NUM_BITS as usize;
+        let digit = pos / bits;
+        digit < self.len() && (self[digit] >> (pos % bits) as u32) & T::Digit::ONE != T::Digit::ZERO
+    }
+
+    fn extract_bits(&self, range: Range<usize>) -> Self
+    where
+        Self: BigNumMut,
+    {
+        let bits = T::Digit::NUM_BITS as usize;
+        let size = range.end.saturating_sub(range.start);
+        assert!(
+            size <= self.len() * bits,
+            "Range must not have more bits than number"
+        );
+
+        let mut res = self.clone();
+        for i in 0..res.len() {
+            let d = if i * bits < size {
+                digit_at_bit(self, range.start + i * bits)
+            } else {
+                T::Digit::ZERO
+            };
+
+            // clear bits above range
+            let rest = size.saturating_sub(i * bits);
+            res[i] = if rest >= bits {
+                d
+            } else if rest > 0 {
+                d & ((!T::Digit::ZERO) >> (T::Digit::NUM_BITS - rest as u32))
+            } else {
+                T::Digit::ZERO
+            };
+        }
+        res
+    }
+
+    fn hamming_distance(&self, other: &impl BigNum<Digit = Self::Digit>) -> usize {
+        let mut res = 0;
+        for i in 0..self.len().max(other.len()) {
+            let l = if i < self.len() {
+                self[i]
+            } else {
+                T::Digit::ZERO
+            };
+            let r = if i < other.len() {
+                other[i]
+            } else {
+                T::Digit::ZERO
+            };
+            res += (l ^ r).count_ones() as usize;
+        }
+        res
+    }
+}
+
+impl<T> BigNumBitsMut for T
+where
+    T: BigNumMut,
+{
+    fn set_bit(&mut self, pos: usize) {
+        let bits = T::Digit::NUM_BITS as usize;
+        assert!(pos < self.len() * bits, "Bit position outside of number");
+        self[pos / bits] |= T::Digit::ONE << (pos % bits) as u32;
+    }
+
+    fn clear_bit(&mut self, pos: usize) {
+        let bits = T::Digit::NUM_BITS as usize;
+        if pos < self.len() * bits {
+            self[pos / bits] &= !(T::Digit::ONE << (pos % bits) as u32);
+        }
+    }
+
+    fn flip_bit(&mut self, pos: usize) {
+        let bits = T::Digit::NUM_BITS as usize;
+        assert!(pos < self.len() * bits, "Bit position outside of number");
+        self[pos / bits] ^= T::Digit::ONE << (pos % bits) as u32;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::num::VecBigNum;
+
+    fn from_u32(v: u32) -> VecBigNum<u8> {
+        VecBigNum::from(Vec::from(&v.to_le_bytes()[..]))
+    }
+
+    const VALUES: &[u32] = &[
+        0,
+        1,
+        2,
+        3,
+        0x80,
+        0x100,
+        0xff00,
+        0x1234_5678,
+        0x8000_0000,
+        0xdead_beef,
+        u32::MAX,
+    ];
+
+    #[test]
+    fn test_inspection() {
+        for &v in VALUES {
+            let num = from_u32(v);
+            assert_eq!(num.bit_len(), 32 - v.leading_zeros() as usize);
+            assert_eq!(BigNumBits::leading_zeros(&num), v.leading_zeros() as usize);
+            assert_eq!(
+                BigNumBits::trailing_zeros(&num),
+                v.trailing_zeros() as usize
+            );
+            assert_eq!(BigNumBits::count_ones(&num), v.count_ones() as usize);
+            for pos in 0..40 {
+                assert_eq!(num.test_bit(pos), pos < 32 && (v >> pos) & 1 == 1);
+            }
+            for &w in VALUES {
+                assert_eq!(
+                    num.hamming_distance(&VecBigNum::from(vec![w as u8])),
+                    (v ^ (w & 0xff)).count_ones() as usize
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_extract_bits() {
+        for &v in VALUES {
+            let num = from_u32(v);
+            for start in 0..32 {
+                for end in start..(start + 32).min(40) {
+                    let size = end - start;
+                    let mask = if size >= 32 {
+                        u32::MAX
+                    } else {
+                        (1 << size) - 1
+                    };
+                    let expected = v.checked_shr(start as u32).unwrap_or(0) & mask;
+                    assert_eq!(num.extract_bits(start..end), from_u32(expected));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_manipulation() {
+        for &v in VALUES {
+            for pos in 0..32 {
+                let mut num = from_u32(v);
+                num.set_bit(pos);
+                assert_eq!(num, from_u32(v | 1 << pos));
+
+                let mut num = from_u32(v);
+                num.clear_bit(pos);
+                assert_eq!(num, from_u32(v & !(1 << pos)));
+
+                let mut num = from_u32(v);
+                num.flip_bit(pos);
+                assert_eq!(num, from_u32(v ^ 1 << pos));
+            }
+        }
+    }
+}
@@ -0,0 +1,534 @@
+use crate::iutil::{div_rem, forward_ref_op, from_u64, mul, pow_u64};
+use crate::num::{
+    BigNum, BigNumBits, DefaultBigNumDigit, DynamicBigNum, RoundingMode, SignedBigNum,
+    SignedBigNumMut, SignedVecBigNum, UnsignedNumDigit, VecBigNum,
+};
+use crate::ops::{SignMagnitudeEngine, SignedEngine, SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::*;
+use core::str::FromStr;
+
+type Engine = SignMagnitudeEngine<SimpleEngine>;
+
+/// Error, which may occur when parsing decimal number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseDecimalError {
+    /// String contains no digits.
+    Empty,
+    /// String contains character, which is neither digit nor sign or decimal point at allowed position.
+    InvalidCharacter,
+    /// Number does not fit in the target type.
+    OutOfRange,
+}
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDecimalError::Empty => write!(f, "no digits"),
+            ParseDecimalError::InvalidCharacter => write!(f, "invalid character"),
+            ParseDecimalError::OutOfRange => write!(f, "number out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ParseDecimalError {}
+
+fn pow10<D>(n: usize) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    pow_u64(&from_u64(10), n as u64)
+}
+
+/// Rounds quotient of division, which left remainder `rem`, in given mode.
+fn round_quotient<D>(
+    mut quot: VecBigNum<D>,
+    rem: &VecBigNum<D>,
+    divisor: &VecBigNum<D>,
+    minus: bool,
+    mode: RoundingMode,
+) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    if rem.is_zero() {
+        return quot;
+    }
+
+    // remainder is compared with half of the divisor
+    let mut twice = rem.clone();
+    SimpleEngine::add_resize(&mut twice, rem);
+    let half = SimpleEngine::compare(&twice, divisor);
+    let increment = match mode {
+        RoundingMode::NearestEven => {
+            half == Ordering::Greater || (half == Ordering::Equal && quot.test_bit(0))
+        }
+        RoundingMode::NearestAway => half != Ordering::Less,
+        RoundingMode::TowardZero => false,
+        RoundingMode::Up => !minus,
+        RoundingMode::Down => minus,
+    };
+    if increment {
+        SimpleEngine::add_resize(&mut quot, &from_u64(1));
+    }
+    quot
+}
+
+/// Decimal number of arbitrary precision.
+///
+/// Value is `unscaled * 10^-scale`. Scale is kept as given, so `1.50` and `1.5` are equal numbers
+/// with different representations and each of them is printed with its own number of fractional digits.
+///
+/// Addition, subtraction and multiplication are exact. Division rounds result to requested scale.
+#[derive(Debug, Clone)]
+pub struct BigDecimal<D = DefaultBigNumDigit> {
+    unscaled: SignedVecBigNum<D>,
+    scale: usize,
+}
+
+impl<D> BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Creates number `unscaled * 10^-scale`.
+    pub fn new(unscaled: SignedVecBigNum<D>, scale: usize) -> Self {
+        let (mut num, minus) = unscaled.into_parts();
+        num.normalize();
+        let minus = minus && !num.is_zero();
+        Self {
+            unscaled: SignedVecBigNum::from_parts(num, minus),
+            scale,
+        }
+    }
+
+    /// Returns zero without fractional digits.
+    pub fn zero() -> Self {
+        Self::new(SignedVecBigNum::new_zeroed(), 0)
+    }
+
+    /// Returns unscaled value.
+    pub fn unscaled(&self) -> &SignedVecBigNum<D> {
+        &self.unscaled
+    }
+
+    /// Returns number of fractional digits.
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
+    /// Returns true if value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.unscaled.magnitude().is_zero()
+    }
+
+    /// Returns true if value is smaller than zero.
+    pub fn is_negative(&self) -> bool {
+        self.unscaled.is_negative()
+    }
+
+    /// Returns absolute value.
+    pub fn abs(&self) -> Self {
+        Self::new(self.unscaled.magnitude().clone().into(), self.scale)
+    }
+
+    /// Returns unscaled value of this number represented with bigger or the same scale.
+    fn aligned(&self, scale: usize) -> SignedVecBigNum<D> {
+        debug_assert!(scale >= self.scale);
+        if scale == self.scale {
+            return self.unscaled.clone();
+        }
+        let num = mul(self.unscaled.magnitude(), &pow10(scale - self.scale));
+        SignedVecBigNum::from_parts(num, self.is_negative())
+    }
+
+    /// Returns this number with given number of fractional digits.
+    /// Digits are appended exactly and removed with rounding in given mode.
+    pub fn rescale(&self, scale: usize, mode: RoundingMode) -> Self {
+        if scale >= self.scale {
+            return Self::new(self.aligned(scale), scale);
+        }
+
+        let divisor = pow10(self.scale - scale);
+        let (quot, rem) = div_rem(self.unscaled.magnitude(), &divisor);
+        let quot = round_quotient(quot, &rem, &divisor, self.is_negative(), mode);
+        Self::new(SignedVecBigNum::from_parts(quot, self.is_negative()), scale)
+    }
+
+    /// Returns the same value with trailing fractional zeros removed.
+    pub fn normalized(&self) -> Self {
+        let ten = from_u64(10);
+        let mut num = self.unscaled.magnitude().clone();
+        let mut scale = self.scale;
+        while scale > 0 {
+            let (quot, rem) = div_rem(&num, &ten);
+            if !rem.is_zero() {
+                break;
+            }
+            num = quot;
+            scale -= 1;
+        }
+        Self::new(SignedVecBigNum::from_parts(num, self.is_negative()), scale)
+    }
+
+    /// Returns `self / rhs` with `scale` fractional digits rounded in given mode.
+    ///
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    pub fn div_with(&self, rhs: &Self, scale: usize, mode: RoundingMode) -> Self {
+        assert!(!rhs.is_zero(), "Division by zero");
+
+        // a/10^sa / (b/10^sb) = q/10^s, so q = a * 10^(s + sb - sa) / b
+        let mut num = self.unscaled.magnitude().clone();
+        let mut den = rhs.unscaled.magnitude().clone();
+        if scale + rhs.scale >= self.scale {
+            num = mul(&num, &pow10(scale + rhs.scale - self.scale));
+        } else {
+            den = mul(&den, &pow10(self.scale - scale - rhs.scale));
+        }
+
+        let minus = self.is_negative() != rhs.is_negative();
+        let (quot, rem) = div_rem(&num, &den);
+        let quot = round_quotient(quot, &rem, &den, minus, mode);
+        Self::new(SignedVecBigNum::from_parts(quot, minus), scale)
+    }
+}
+
+impl<D> From<SignedVecBigNum<D>> for BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Creates number without fractional digits.
+    fn from(num: SignedVecBigNum<D>) -> Self {
+        Self::new(num, 0)
+    }
+}
+
+impl<D> PartialEq for BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Compares values, scale is ignored.
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<D> Eq for BigDecimal<D> where D: UnsignedNumDigit {}
+
+impl<D> PartialOrd for BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D> Ord for BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        self.aligned(scale).cmp(&other.aligned(scale))
+    }
+}
+
+impl<D> Hash for BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Hashes value, so numbers differing only in scale have the same hash.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalized();
+        normalized.unscaled.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+impl<D> fmt::Display for BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Formats number in decimal with all its fractional digits.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.unscaled.magnitude().to_string();
+        if self.scale == 0 {
+            return f.pad_integral(!self.is_negative(), "", &digits);
+        }
+
+        let digits = format!("{:0>width$}", digits, width = self.scale + 1);
+        let point = digits.len() - self.scale;
+        let text = format!("{}.{}", &digits[..point], &digits[point..]);
+        f.pad_integral(!self.is_negative(), "", &text)
+    }
+}
+
+impl<D> FromStr for BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Err = ParseDecimalError;
+
+    /// Parses number like `-123.4500`, keeping all its fractional digits.
+    /// Sign is optional and either integer or fractional part may be omitted, but not both.
+    fn from_str(text: &str) -> Result<Self, ParseDecimalError> {
+        let (minus, text) = match text.as_bytes().first() {
+            Some(b'-') => (true, &text[1..]),
+            Some(b'+') => (false, &text[1..]),
+            _ => (false, text),
+        };
+        let (int_part, frac_part) = match text.find('.') {
+            Some(pos) => (&text[..pos], &text[pos + 1..]),
+            None => (text, ""),
+        };
+        if int_part.is_empty() && frac_part.is_empty() {
+            return Err(ParseDecimalError::Empty);
+        }
+
+        let digits = int_part
+            .bytes()
+            .chain(frac_part.bytes())
+            .collect::<Vec<_>>();
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return Err(ParseDecimalError::InvalidCharacter);
+        }
+
+        // digits are processed in chunks, which fit in u64
+        let mut num = VecBigNum::new_zeroed();
+        for chunk in digits.chunks(18) {
+            let value = chunk
+                .iter()
+                .fold(0, |acc, d| acc * 10 + u64::from(d - b'0'));
+            num = mul(&num, &from_u64(10u64.pow(chunk.len() as u32)));
+            SimpleEngine::add_resize(&mut num, &from_u64(value));
+        }
+        Ok(Self::new(
+            SignedVecBigNum::from_parts(num, minus),
+            frac_part.len(),
+        ))
+    }
+}
+
+impl<D> Add for &BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = BigDecimal<D>;
+
+    /// Adds numbers exactly, result has the bigger scale of operands.
+    fn add(self, rhs: Self) -> BigDecimal<D> {
+        let scale = self.scale.max(rhs.scale);
+        let mut num = self.aligned(scale);
+        Engine::add_resize(&mut num, &rhs.aligned(scale));
+        BigDecimal::new(num, scale)
+    }
+}
+
+impl<D> Sub for &BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = BigDecimal<D>;
+
+    /// Subtracts numbers exactly, result has the bigger scale of operands.
+    fn sub(self, rhs: Self) -> BigDecimal<D> {
+        let scale = self.scale.max(rhs.scale);
+        let mut num = self.aligned(scale);
+        Engine::sub_resize(&mut num, &rhs.aligned(scale));
+        BigDecimal::new(num, scale)
+    }
+}
+
+impl<D> Mul for &BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = BigDecimal<D>;
+
+    /// Multiplies numbers exactly, result's scale is the sum of operands' scales.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> BigDecimal<D> {
+        let mut num = SignedVecBigNum::new_zeroed();
+        Engine::mul_resize(&mut num, &self.unscaled, &rhs.unscaled);
+        BigDecimal::new(num, self.scale + rhs.scale)
+    }
+}
+
+impl<D> Neg for &BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = BigDecimal<D>;
+
+    fn neg(self) -> BigDecimal<D> {
+        let mut res = self.clone();
+        if !res.is_zero() {
+            res.unscaled.set_minus(!res.unscaled.is_minus());
+        }
+        res
+    }
+}
+
+impl<D> Neg for BigDecimal<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        -&self
+    }
+}
+
+forward_ref_op!([D] BigDecimal<D>, Add, add, AddAssign, add_assign);
+forward_ref_op!([D] BigDecimal<D>, Sub, sub, SubAssign, sub_assign);
+forward_ref_op!([D] BigDecimal<D>, Mul, mul, MulAssign, mul_assign);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dec(text: &str) -> BigDecimal<u32> {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for &text in &[
+            "-123.4500",
+            "0",
+            "0.001",
+            "-0.50",
+            "12345678901234567890.123456789",
+            "7",
+        ] {
+            assert_eq!(dec(text).to_string(), text);
+        }
+        assert_eq!(dec("+.5").to_string(), "0.5");
+        assert_eq!(dec("-0.000").to_string(), "0.000");
+        assert_eq!(dec("3.").scale(), 0);
+        assert_eq!(dec("-123.4500").scale(), 4);
+        assert_eq!(format!("{:>8}", dec("-1.5")), "    -1.5");
+
+        let parse = |text: &str| text.parse::<BigDecimal<u32>>().err();
+        assert_eq!(parse(""), Some(ParseDecimalError::Empty));
+        assert_eq!(parse("-."), Some(ParseDecimalError::Empty));
+        assert_eq!(parse("1.2.3"), Some(ParseDecimalError::InvalidCharacter));
+        assert_eq!(parse("1e5"), Some(ParseDecimalError::InvalidCharacter));
+        assert_eq!(parse("--1"), Some(ParseDecimalError::InvalidCharacter));
+    }
+
+    #[test]
+    fn test_exact_arithmetic() {
+        assert_eq!((dec("0.1") + dec("0.2")).to_string(), "0.3");
+        assert_eq!((dec("1.50") - dec("2.125")).to_string(), "-0.625");
+        assert_eq!((dec("-1.5") * dec("0.20")).to_string(), "-0.300");
+        assert_eq!((-dec("2.00")).to_string(), "-2.00");
+
+        let mut sum = BigDecimal::zero();
+        for _ in 0..10 {
+            sum += dec("0.10");
+        }
+        assert_eq!(sum.to_string(), "1.00");
+        assert_eq!(sum, dec("1"));
+    }
+
+    #[test]
+    fn test_rounding() {
+        let round = |text: &str, mode| dec(text).rescale(0, mode).to_string();
+        let cases = [
+            ("2.5", ["2", "3", "2", "3", "2"]),
+            ("3.5", ["4", "4", "3", "4", "3"]),
+            ("-2.5", ["-2", "-3", "-2", "-2", "-3"]),
+            ("2.51", ["3", "3", "2", "3", "2"]),
+            ("-2.49", ["-2", "-2", "-2", "-2", "-3"]),
+            ("4.0", ["4", "4", "4", "4", "4"]),
+        ];
+        let modes = [
+            RoundingMode::NearestEven,
+            RoundingMode::NearestAway,
+            RoundingMode::TowardZero,
+            RoundingMode::Up,
+            RoundingMode::Down,
+        ];
+        for (text, expected) in cases.iter() {
+            for (&mode, &expected) in modes.iter().zip(expected.iter()) {
+                assert_eq!(round(text, mode), expected, "{} {:?}", text, mode);
+            }
+        }
+
+        assert_eq!(
+            dec("-0.4")
+                .rescale(0, RoundingMode::NearestEven)
+                .to_string(),
+            "0"
+        );
+        assert_eq!(
+            dec("1.5").rescale(3, RoundingMode::NearestEven).to_string(),
+            "1.500"
+        );
+        assert_eq!(
+            dec("1.2345")
+                .rescale(2, RoundingMode::NearestEven)
+                .to_string(),
+            "1.23"
+        );
+        assert_eq!(
+            dec("1.2350")
+                .rescale(2, RoundingMode::NearestEven)
+                .to_string(),
+            "1.24"
+        );
+        assert_eq!(
+            dec("1.2450")
+                .rescale(2, RoundingMode::NearestEven)
+                .to_string(),
+            "1.24"
+        );
+        assert_eq!(dec("100.00").normalized().to_string(), "100");
+        assert_eq!(dec("-0.0250").normalized().to_string(), "-0.025");
+    }
+
+    #[test]
+    fn test_division() {
+        let div = |a: &str, b: &str, scale, mode| dec(a).div_with(&dec(b), scale, mode).to_string();
+        assert_eq!(div("1", "3", 5, RoundingMode::NearestEven), "0.33333");
+        assert_eq!(div("2", "3", 5, RoundingMode::NearestEven), "0.66667");
+        assert_eq!(div("-2", "3", 5, RoundingMode::TowardZero), "-0.66666");
+        assert_eq!(div("-2", "3", 2, RoundingMode::Up), "-0.66");
+        assert_eq!(div("-2", "3", 2, RoundingMode::Down), "-0.67");
+        assert_eq!(div("10.00", "4", 1, RoundingMode::NearestEven), "2.5");
+        assert_eq!(div("10.00", "4", 0, RoundingMode::NearestEven), "2");
+        assert_eq!(div("10.00", "4", 0, RoundingMode::NearestAway), "3");
+        assert_eq!(
+            div("1.000000", "0.001", 0, RoundingMode::NearestEven),
+            "1000"
+        );
+        assert_eq!(
+            div("123.45", "-0.5", 3, RoundingMode::NearestEven),
+            "-246.900"
+        );
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert_eq!(dec("1.50"), dec("1.5"));
+        assert!(dec("-1.50") < dec("-1.49"));
+        assert!(dec("0.001") > dec("0"));
+        assert_eq!(dec("0.0"), dec("-0"));
+
+        let mut set = std::collections::HashSet::new();
+        set.insert(dec("2.50"));
+        assert!(set.contains(&dec("2.5")));
+        assert!(!set.contains(&dec("25")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_division_by_zero() {
+        dec("1").div_with(&dec("0.00"), 2, RoundingMode::NearestEven);
+    }
+}
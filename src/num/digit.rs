@@ -1,3 +1,4 @@
+use crate::num::{DoubleDigit, DoubleU128, DoubleUsize};
 use core::convert::TryFrom;
 use core::hash::Hash;
 use core::ops::*;
@@ -62,7 +63,7 @@ pub trait UnsignedNumDigit:
     type Exponent: UnsignedNumDigit;
 
     /// Number that is twice as big as current one.
-    // type Double: UnsignedNumDigit;
+    type Double: DoubleDigit<Self>;
 
     const ZERO: Self;
     const ONE: Self;
@@ -70,6 +71,9 @@ pub trait UnsignedNumDigit:
     const MIN: Self;
     const NUM_BITS: u32;
 
+    fn count_ones(self) -> u32;
+    fn leading_zeros(self) -> u32;
+    fn trailing_zeros(self) -> u32;
     fn pow(self, other: Self::Exponent) -> Self;
     fn wrapping_add(self, other: Self) -> Self;
     fn wrapping_sub(self, other: Self) -> Self;
@@ -111,7 +115,64 @@ pub trait UnsignedNumDigit:
 
     //// Multiples two numbes, so that higher NUM_BITS are in 1st self and
     //// lower NUM_BITS are in 2nd self
-    fn mul_to_parts(self, other: Self) -> (Self, Self);
+    #[inline]
+    fn mul_to_parts(self, other: Self) -> (Self, Self) {
+        (Self::Double::from_digit(self) * Self::Double::from_digit(other)).into_parts()
+    }
+
+    /// Divides two digit number, which has `self` as higher digit and `lo` as lower one, by `divisor`.
+    /// Returns quotient and remainder.
+    ///
+    /// # Panic
+    /// Panics when `divisor` is zero or when quotient does not fit single digit, which happens when `self >= divisor`.
+    #[inline]
+    fn div_wide(self, lo: Self, divisor: Self) -> (Self, Self) {
+        assert!(divisor != Self::ZERO, "Division by zero");
+        assert!(self < divisor, "Quotient does not fit single digit");
+
+        let num = Self::Double::from_parts(self, lo);
+        let divisor = Self::Double::from_digit(divisor);
+        let (quot, rem) = num.div_rem(divisor);
+        (quot.into_parts().1, rem.into_parts().1)
+    }
+
+    /// Computes `self + other + carry`.
+    /// Returns sum and carry out.
+    #[inline]
+    fn digit_carrying_add(self, other: Self, carry: bool) -> (Self, bool) {
+        let (n, overflow) = self.overflowing_add(other);
+        let (n, carry_overflow) = n.overflowing_add(if carry { Self::ONE } else { Self::ZERO });
+        (n, overflow || carry_overflow)
+    }
+
+    /// Computes `self - other - borrow`.
+    /// Returns difference and borrow out.
+    #[inline]
+    fn digit_borrowing_sub(self, other: Self, borrow: bool) -> (Self, bool) {
+        let (n, overflow) = self.overflowing_sub(other);
+        let (n, borrow_overflow) = n.overflowing_sub(if borrow { Self::ONE } else { Self::ZERO });
+        (n, overflow || borrow_overflow)
+    }
+
+    /// Computes `self * other` without overflow.
+    /// Returns lower and higher digit of result, just like primitive integers do.
+    #[inline]
+    fn digit_widening_mul(self, other: Self) -> (Self, Self) {
+        let (hi, lo) = self.mul_to_parts(other);
+        (lo, hi)
+    }
+
+    /// Computes `self * other + carry + add` without overflow, since result always fits two digits.
+    /// Returns lower and higher digit of result.
+    #[inline]
+    fn digit_carrying_mul(self, other: Self, carry: Self, add: Self) -> (Self, Self) {
+        let (hi, lo) = self.mul_to_parts(other);
+        let (lo, overflow) = lo.overflowing_add(carry);
+        let hi = if overflow { hi + Self::ONE } else { hi };
+        let (lo, overflow) = lo.overflowing_add(add);
+        let hi = if overflow { hi + Self::ONE } else { hi };
+        (lo, hi)
+    }
 }
 
 /// Type of single digit used in bignum operations.
@@ -166,7 +227,7 @@ pub trait SignedNumDigit:
     /// Type of exponent used for pow.
     type Exponent: UnsignedNumDigit;
 
-    /// Number that is twice as big as current one.
+    // Number that is twice as big as current one.
     // type Double: SignedNumDigit;
 
     const ZERO: Self;
@@ -306,7 +367,7 @@ macro_rules! derive_unsigned_num_digit {
         impl UnsignedNumDigit for $type {
             type Signed = $signed;
             type Exponent = u32;
-            // type Double = $double;
+            type Double = $double;
 
             const ZERO: Self = 0 as Self;
             const ONE: Self = 1 as Self;
@@ -315,6 +376,9 @@ macro_rules! derive_unsigned_num_digit {
             const NUM_BITS: u32 = (core::mem::size_of::<$type>() as u32) * 8;
 
             generate_mapping! {
+                fn count_ones() -> u32;
+                fn leading_zeros() -> u32;
+                fn trailing_zeros() -> u32;
                 fn pow(exp: Self::Exponent) -> Self;
                 fn wrapping_add(other: Self) -> Self;
                 fn wrapping_sub(other: Self) -> Self;
@@ -354,15 +418,6 @@ macro_rules! derive_unsigned_num_digit {
                 fn overflowing_div_euclid(other: Self) -> (Self, bool);
                 fn overflowing_rem_euclid(other: Self) -> (Self, bool);
             }
-
-            #[inline]
-            fn mul_to_parts(self, other: Self) -> (Self, Self) {
-                let res = (self as $double) * (other as $double);
-                let ones = ((!(0 as $type)) as $double);
-                let lower_bits = (res & ones) as $type;
-                let higher_bits = ((res >> Self::NUM_BITS) & ones) as $type;
-                (higher_bits, lower_bits)
-            }
         }
     };
 }
@@ -371,12 +426,122 @@ derive_unsigned_num_digit!(u8, i8, u16);
 derive_unsigned_num_digit!(u16, i16, u32);
 derive_unsigned_num_digit!(u32, i32, u64);
 derive_unsigned_num_digit!(u64, i64, u128);
-// derive_unsigned_num_digit!(u128, i128, u128);
-// derive_unsigned_num_digit!(usize, isize, );
+derive_unsigned_num_digit!(u128, i128, DoubleU128);
+derive_unsigned_num_digit!(usize, isize, DoubleUsize);
 
 derive_signed_num_digit!(i8, u8, i16);
 derive_signed_num_digit!(i16, u16, i32);
 derive_signed_num_digit!(i32, u32, i64);
 derive_signed_num_digit!(i64, u64, i128);
-// derive_signed_num_digit!(i128, u128, i128);
-// derive_signed_num_digit!(isize, usize);
+derive_signed_num_digit!(i128, u128, i128);
+derive_signed_num_digit!(isize, usize, isize);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::num::{DynamicBigNum, VecBigNum};
+    use crate::ops::{SimpleEngine, UnsignedEngine};
+
+    #[test]
+    fn test_mul_to_parts_u128() {
+        let values = [
+            0u128,
+            1,
+            2,
+            u64::MAX as u128,
+            u64::MAX as u128 + 1,
+            0x0123_4567_89ab_cdef_fedc_ba98_7654_3210,
+            u128::MAX - 1,
+            u128::MAX,
+        ];
+        for &a in values.iter() {
+            for &b in values.iter() {
+                let (hi, lo) = a.mul_to_parts(b);
+                assert_eq!(lo, a.wrapping_mul(b));
+
+                // compare with multiplication of numbers with u64 digits
+                let lhs = VecBigNum::from(vec![a as u64, (a >> 64) as u64]);
+                let rhs = VecBigNum::from(vec![b as u64, (b >> 64) as u64]);
+                let mut res = VecBigNum::new_zeroed_sized(4);
+                SimpleEngine::mul_accumulate(&mut res, &lhs, &rhs);
+                let res = res.into_inner();
+                let expected_hi = (res[3] as u128) << 64 | res[2] as u128;
+                assert_eq!(hi, expected_hi, "{} * {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_to_parts_usize() {
+        let (hi, lo) = usize::MAX.mul_to_parts(usize::MAX);
+        assert_eq!(hi, usize::MAX - 1);
+        assert_eq!(lo, 1);
+    }
+
+    #[test]
+    fn test_wide_primitives_u8() {
+        for a in 0..=255u8 {
+            for b in (0..=255u8).step_by(3) {
+                for &carry in [false, true].iter() {
+                    let expected = a as u16 + b as u16 + carry as u16;
+                    let (n, c) = UnsignedNumDigit::digit_carrying_add(a, b, carry);
+                    assert_eq!((n, c), (expected as u8, expected > 0xff));
+
+                    let expected = a as i16 - b as i16 - carry as i16;
+                    let (n, c) = UnsignedNumDigit::digit_borrowing_sub(a, b, carry);
+                    assert_eq!((n, c), (expected as u8, expected < 0));
+                }
+
+                let expected = a as u16 * b as u16;
+                let (lo, hi) = UnsignedNumDigit::digit_widening_mul(a, b);
+                assert_eq!((lo, hi), (expected as u8, (expected >> 8) as u8));
+
+                let expected = a as u16 * b as u16 + 0xff + 0xfe;
+                let (lo, hi) = UnsignedNumDigit::digit_carrying_mul(a, b, 0xff, 0xfe);
+                assert_eq!((lo, hi), (expected as u8, (expected >> 8) as u8));
+
+                if b != 0 && a < b {
+                    for &lo in [0u8, 1, 0x7f, 0xff].iter() {
+                        let num = (a as u16) << 8 | lo as u16;
+                        let (q, r) = UnsignedNumDigit::div_wide(a, lo, b);
+                        assert_eq!((q as u16, r as u16), (num / b as u16, num % b as u16));
+                    }
+                }
+            }
+            assert_eq!(UnsignedNumDigit::leading_zeros(a), a.leading_zeros());
+            assert_eq!(UnsignedNumDigit::trailing_zeros(a), a.trailing_zeros());
+        }
+    }
+
+    #[test]
+    fn test_div_wide_u128() {
+        let values = [
+            1u128,
+            2,
+            u64::MAX as u128,
+            0x0123_4567_89ab_cdef_fedc_ba98_7654_3210,
+            u128::MAX - 1,
+            u128::MAX,
+        ];
+        for &divisor in values.iter() {
+            for &hi in values.iter().filter(|&&hi| hi < divisor) {
+                for &lo in values.iter() {
+                    let (q, r) = UnsignedNumDigit::div_wide(hi, lo, divisor);
+                    assert!(r < divisor);
+
+                    // q * divisor + r must be equal to hi:lo
+                    let (p_hi, p_lo) = q.mul_to_parts(divisor);
+                    let (n_lo, carry) = p_lo.overflowing_add(r);
+                    let n_hi = p_hi + carry as u128;
+                    assert_eq!((n_hi, n_lo), (hi, lo));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_wide_quotient_does_not_fit() {
+        UnsignedNumDigit::div_wide(3u32, 0, 3);
+    }
+}
@@ -0,0 +1,440 @@
+use crate::num::UnsignedNumDigit;
+use core::hash::Hash;
+use core::ops::*;
+
+/// Unsigned number, which is twice as big as digit `D`.
+/// It's used for intermediate results of digit operations, like multiplication or division of two digit numbers.
+///
+/// Operators behave like ones of primitive integers, so overflow is not allowed.
+pub trait DoubleDigit<D>:
+    Sized
+    + core::fmt::Debug
+    + Copy
+    + Clone
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Not<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+    + PartialEq
+    + Eq
+    + Hash
+    + PartialOrd
+    + Ord
+    + Default
+where
+    D: UnsignedNumDigit,
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const MAX: Self;
+
+    /// Creates double digit with value of single digit.
+    fn from_digit(d: D) -> Self;
+
+    /// Creates double digit from higher and lower digit.
+    fn from_parts(hi: D, lo: D) -> Self;
+
+    /// Splits double digit into higher and lower digit, in the same order as `mul_to_parts` returns them.
+    fn into_parts(self) -> (D, D);
+
+    /// Computes quotient and remainder at once.
+    ///
+    /// # Panic
+    /// Panics when `other` is zero.
+    #[inline]
+    fn div_rem(self, other: Self) -> (Self, Self) {
+        (self / other, self % other)
+    }
+}
+
+macro_rules! derive_double_digit {
+    ($type:ident, $double:ident) => {
+        impl DoubleDigit<$type> for $double {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const MAX: Self = !0;
+
+            #[inline]
+            fn from_digit(d: $type) -> Self {
+                d as Self
+            }
+
+            #[inline]
+            fn from_parts(hi: $type, lo: $type) -> Self {
+                ((hi as Self) << <$type as UnsignedNumDigit>::NUM_BITS) | lo as Self
+            }
+
+            #[inline]
+            fn into_parts(self) -> ($type, $type) {
+                (
+                    (self >> <$type as UnsignedNumDigit>::NUM_BITS) as $type,
+                    self as $type,
+                )
+            }
+        }
+    };
+}
+
+#[cfg(target_pointer_width = "16")]
+pub(crate) type DoubleUsize = u32;
+#[cfg(target_pointer_width = "32")]
+pub(crate) type DoubleUsize = u64;
+#[cfg(target_pointer_width = "64")]
+pub(crate) type DoubleUsize = u128;
+
+derive_double_digit!(u8, u16);
+derive_double_digit!(u16, u32);
+derive_double_digit!(u32, u64);
+derive_double_digit!(u64, u128);
+derive_double_digit!(usize, DoubleUsize);
+
+/// Multiplies two u128 using halves of them, since there is no primitive twice as big.
+/// Returns higher and lower part of result.
+#[inline]
+fn mul_u128(a: u128, b: u128) -> (u128, u128) {
+    let half = 64;
+    let mask = u64::MAX as u128;
+
+    let (a_hi, a_lo) = (a >> half, a & mask);
+    let (b_hi, b_lo) = (b >> half, b & mask);
+
+    let ll = a_lo * b_lo;
+    let lh = a_lo * b_hi;
+    let hl = a_hi * b_lo;
+    let hh = a_hi * b_hi;
+
+    // can't overflow: sum of three values smaller than 2^half
+    let mid = (ll >> half) + (lh & mask) + (hl & mask);
+
+    let lower_bits = (ll & mask) | (mid << half);
+    let higher_bits = hh + (lh >> half) + (hl >> half) + (mid >> half);
+    (higher_bits, lower_bits)
+}
+
+/// Divides two u128 digit number `hi:lo` by `divisor` using 64 bit halves as digits, like Knuth's algorithm D does.
+/// Requires `hi < divisor`, so quotient fits u128.
+/// Returns quotient and remainder.
+fn div_wide_u128(hi: u128, lo: u128, divisor: u128) -> (u128, u128) {
+    debug_assert!(hi < divisor);
+    let half = 64;
+    let base = 1u128 << half;
+    let mask = u64::MAX as u128;
+
+    // normalize, so that the most significant bit of divisor is set
+    let shift = divisor.leading_zeros();
+    let v = divisor << shift;
+    let (v1, v0) = (v >> half, v & mask);
+    let u32 = if shift == 0 {
+        hi
+    } else {
+        (hi << shift) | (lo >> (128 - shift))
+    };
+    let u10 = lo << shift;
+    let (u1, u0) = (u10 >> half, u10 & mask);
+
+    // computes single half of quotient of `u:next` divided by `v`, where `u < v`
+    let div_half = |u: u128, next: u128| {
+        let mut q = u / v1;
+        let mut r = u - q * v1;
+        while q >= base || q * v0 > (r << half) + next {
+            q -= 1;
+            r += v1;
+            if r >= base {
+                break;
+            }
+        }
+        // exact value is smaller than `v`, so wrapping operations give it
+        let rem = (u << half)
+            .wrapping_add(next)
+            .wrapping_sub(q.wrapping_mul(v));
+        (q, rem)
+    };
+
+    let (q1, u21) = div_half(u32, u1);
+    let (q0, rem) = div_half(u21, u0);
+    ((q1 << half) | q0, rem >> shift)
+}
+
+/// Software 256 bit unsigned number, which is double digit of `u128`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DoubleU128 {
+    // order of fields matters for derived ordering
+    hi: u128,
+    lo: u128,
+}
+
+impl DoubleDigit<u128> for DoubleU128 {
+    const ZERO: Self = Self { hi: 0, lo: 0 };
+    const ONE: Self = Self { hi: 0, lo: 1 };
+    const MAX: Self = Self {
+        hi: u128::MAX,
+        lo: u128::MAX,
+    };
+
+    #[inline]
+    fn from_digit(d: u128) -> Self {
+        Self { hi: 0, lo: d }
+    }
+
+    #[inline]
+    fn from_parts(hi: u128, lo: u128) -> Self {
+        Self { hi, lo }
+    }
+
+    #[inline]
+    fn into_parts(self) -> (u128, u128) {
+        (self.hi, self.lo)
+    }
+
+    /// Computes quotient and remainder at once.
+    ///
+    /// Quotient, which fits single digit, as in `div_wide`, is computed with 64 bit halves as digits.
+    /// Other cases use shift-subtract division.
+    fn div_rem(self, other: Self) -> (Self, Self) {
+        assert!(other != Self::ZERO, "attempt to divide by zero");
+
+        if other.hi == 0 && self.hi < other.lo {
+            let (quot, rem) = div_wide_u128(self.hi, self.lo, other.lo);
+            return (Self::from_digit(quot), Self::from_digit(rem));
+        }
+
+        // bits above the most significant one of self can't produce quotient bits
+        let bit_len = if self.hi != 0 {
+            256 - self.hi.leading_zeros()
+        } else {
+            128 - self.lo.leading_zeros()
+        };
+
+        let mut quot = Self::ZERO;
+        let mut rem = Self::ZERO;
+        for bit in (0..bit_len).rev() {
+            // if bit is shifted out of rem, then rem is for sure bigger than divisor
+            let shifted_out = rem.hi >> 127 != 0;
+            rem = (rem << 1) | ((self >> bit) & Self::ONE);
+            if shifted_out || rem >= other {
+                let (lo, borrow) = rem.lo.overflowing_sub(other.lo);
+                let hi = rem.hi.wrapping_sub(other.hi).wrapping_sub(borrow as u128);
+                rem = Self { hi, lo };
+                quot = quot | (Self::ONE << bit);
+            }
+        }
+        (quot, rem)
+    }
+}
+
+impl Add for DoubleU128 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        let (lo, carry) = self.lo.overflowing_add(other.lo);
+        Self {
+            hi: self.hi + other.hi + carry as u128,
+            lo,
+        }
+    }
+}
+
+impl Sub for DoubleU128 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        let (lo, borrow) = self.lo.overflowing_sub(other.lo);
+        Self {
+            hi: self.hi - other.hi - borrow as u128,
+            lo,
+        }
+    }
+}
+
+impl Mul for DoubleU128 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        debug_assert!(
+            self.hi == 0 || other.hi == 0,
+            "attempt to multiply with overflow"
+        );
+        let (hi, lo) = mul_u128(self.lo, other.lo);
+        Self {
+            hi: hi + self.hi * other.lo + self.lo * other.hi,
+            lo,
+        }
+    }
+}
+
+impl Div for DoubleU128 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for DoubleU128 {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        self.div_rem(other).1
+    }
+}
+
+impl BitAnd for DoubleU128 {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Self {
+            hi: self.hi & other.hi,
+            lo: self.lo & other.lo,
+        }
+    }
+}
+
+impl BitOr for DoubleU128 {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, other: Self) -> Self {
+        Self {
+            hi: self.hi | other.hi,
+            lo: self.lo | other.lo,
+        }
+    }
+}
+
+impl Not for DoubleU128 {
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self {
+        Self {
+            hi: !self.hi,
+            lo: !self.lo,
+        }
+    }
+}
+
+impl Shl<u32> for DoubleU128 {
+    type Output = Self;
+
+    #[inline]
+    fn shl(self, n: u32) -> Self {
+        assert!(n < 256, "attempt to shift left with overflow");
+        match n {
+            0 => self,
+            1..=127 => Self {
+                hi: (self.hi << n) | (self.lo >> (128 - n)),
+                lo: self.lo << n,
+            },
+            _ => Self {
+                hi: self.lo << (n - 128),
+                lo: 0,
+            },
+        }
+    }
+}
+
+impl Shr<u32> for DoubleU128 {
+    type Output = Self;
+
+    #[inline]
+    fn shr(self, n: u32) -> Self {
+        assert!(n < 256, "attempt to shift right with overflow");
+        match n {
+            0 => self,
+            1..=127 => Self {
+                hi: self.hi >> n,
+                lo: (self.lo >> n) | (self.hi << (128 - n)),
+            },
+            _ => Self {
+                hi: 0,
+                lo: self.hi >> (n - 128),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::iutil::xorshift;
+
+    const VALUES: &[u128] = &[
+        0,
+        1,
+        2,
+        u64::MAX as u128,
+        u64::MAX as u128 + 1,
+        0x0123_4567_89ab_cdef_fedc_ba98_7654_3210,
+        u128::MAX - 1,
+        u128::MAX,
+    ];
+
+    #[test]
+    fn test_double_u128_arithmetic() {
+        for &a in VALUES {
+            for &b in VALUES {
+                let (hi, lo) = mul_u128(a, b);
+                let product = DoubleU128::from_digit(a) * DoubleU128::from_digit(b);
+                assert_eq!(product.into_parts(), (hi, lo));
+
+                let sum = DoubleU128::from_digit(a) + DoubleU128::from_digit(b);
+                let (expected, carry) = a.overflowing_add(b);
+                assert_eq!(sum.into_parts(), (carry as u128, expected));
+                assert_eq!(sum - DoubleU128::from_digit(b), DoubleU128::from_digit(a));
+
+                if b != 0 {
+                    // (a:lo) = q * b + r
+                    let num = DoubleU128::from_parts(a, b);
+                    let divisor = DoubleU128::from_digit(b);
+                    let (q, r) = (num / divisor, num % divisor);
+                    assert!(r < divisor);
+                    if q.into_parts().0 == 0 {
+                        assert_eq!(q * divisor + r, num);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_double_u128_div_wide() {
+        let mut state = 0x0123_4567_89ab_cdefu64;
+        let mut next = || xorshift(&mut state);
+        for i in 0..2000 {
+            let divisor = ((next() as u128) << 64 | next() as u128) >> (i % 128);
+            let divisor = divisor.max(1);
+            let hi = ((next() as u128) << 64 | next() as u128) % divisor;
+            let lo = (next() as u128) << 64 | next() as u128;
+
+            let num = DoubleU128::from_parts(hi, lo);
+            let (q, r) = num.div_rem(DoubleU128::from_digit(divisor));
+            assert_eq!(q.into_parts().0, 0);
+            assert!(r < DoubleU128::from_digit(divisor));
+            assert_eq!(q * DoubleU128::from_digit(divisor) + r, num);
+            assert_eq!(
+                hi.div_wide(lo, divisor),
+                (q.into_parts().1, r.into_parts().1)
+            );
+        }
+    }
+
+    #[test]
+    fn test_double_u128_shifts() {
+        let num = DoubleU128::from_parts(0x0123_4567_89ab_cdef, u128::MAX);
+        for n in 0..256 {
+            assert_eq!((num << n) >> n, num & (DoubleU128::MAX >> n));
+        }
+        assert_eq!(DoubleU128::ONE << 128, DoubleU128::from_parts(1, 0));
+        assert!(DoubleU128::from_parts(1, 0) > DoubleU128::from_digit(u128::MAX));
+    }
+}
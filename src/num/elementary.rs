@@ -0,0 +1,864 @@
+use crate::iutil::{div, div_rem, from_u64, mul, pow_u64, shift_left, shift_right, to_u64};
+use crate::num::float::isqrt;
+use crate::num::{
+    BigFloat, BigNum, BigNumBits, DynamicBigNum, RoundingMode, SignedVecBigNum, UnsignedNumDigit,
+    VecBigNum,
+};
+use crate::ops::{SignMagnitudeEngine, SignedEngine, SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+
+type Engine = SignMagnitudeEngine<SimpleEngine>;
+
+// Functions below work on fixed point numbers, which are integers scaled by `2^scale`.
+// Each of them returns bound of its error in units of the last place, so results can be rounded correctly.
+
+fn pow2<D>(n: usize) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = from_u64(1);
+    shift_left(&mut res, n as u64);
+    res
+}
+
+fn add<D>(lhs: &VecBigNum<D>, rhs: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = lhs.clone();
+    SimpleEngine::add_resize(&mut res, rhs);
+    res
+}
+
+/// Subtracts numbers, `lhs` must not be smaller than `rhs`.
+fn sub<D>(lhs: &VecBigNum<D>, rhs: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = lhs.clone();
+    let borrow = SimpleEngine::sub_resize(&mut res, rhs);
+    debug_assert!(!borrow);
+    res.normalize();
+    res
+}
+
+/// Returns `lhs - rhs` as signed number.
+fn sub_signed<D>(lhs: VecBigNum<D>, rhs: VecBigNum<D>) -> SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = SignedVecBigNum::from(lhs);
+    Engine::sub_resize(&mut res, &SignedVecBigNum::from(rhs));
+    res
+}
+
+/// Multiplies fixed point numbers, error of the result is below one unit.
+fn mul_fixed<D>(lhs: &VecBigNum<D>, rhs: &VecBigNum<D>, scale: usize) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = mul(lhs, rhs);
+    shift_right(&mut res, scale as u64);
+    res
+}
+
+fn div_u64<D>(lhs: &VecBigNum<D>, rhs: u64) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    div(lhs, &from_u64(rhs))
+}
+
+fn bit_len(n: u64) -> usize {
+    (64 - n.leading_zeros()) as usize
+}
+
+/// Returns number of additional bits, which keep accumulated errors of computation with `scale` bits away from
+/// its result.
+fn guard_bits(scale: usize) -> usize {
+    bit_len(scale as u64) + 8
+}
+
+/// Drops `n` least significant bits of fixed point number, which has given error.
+fn drop_bits<D>(value: SignedVecBigNum<D>, n: usize, error: u64) -> (SignedVecBigNum<D>, u64)
+where
+    D: UnsignedNumDigit,
+{
+    let (mut value, minus) = value.into_parts();
+    shift_right(&mut value, n as u64);
+    (SignedVecBigNum::from_parts(value, minus), (error >> n) + 2)
+}
+
+/// Returns `|x| * 2^scale` rounded down.
+fn to_fixed<D>(x: &BigFloat<D>, scale: usize) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut mantissa = x.mantissa().into_parts().0;
+    let shift = x.exponent() + scale as i64;
+    if shift >= 0 {
+        shift_left(&mut mantissa, shift as u64);
+    } else {
+        shift_right(&mut mantissa, shift.unsigned_abs());
+    }
+    mantissa
+}
+
+/// Returns position of the bit just above the most significant set bit of nonzero number.
+fn top<D>(x: &BigFloat<D>) -> i64
+where
+    D: UnsignedNumDigit,
+{
+    x.exponent() + x.mantissa().magnitude().bit_len() as i64
+}
+
+/// Returns `atan(1/n) * 2^scale`, or `atanh(1/n) * 2^scale` when `hyperbolic` is set.
+fn arctan_inv<D>(n: u64, hyperbolic: bool, scale: usize) -> (VecBigNum<D>, u64)
+where
+    D: UnsignedNumDigit,
+{
+    let n2 = from_u64(n * n);
+    let mut power = div_u64(&pow2(scale), n);
+    let mut positive = VecBigNum::new_zeroed();
+    let mut negative = VecBigNum::new_zeroed();
+    // once the power becomes zero, the rest of the series is below two units
+    let mut error = 2;
+    let mut k = 0;
+    while !power.is_zero() {
+        let term = div_u64(&power, 2 * k + 1);
+        if hyperbolic || k % 2 == 0 {
+            SimpleEngine::add_resize(&mut positive, &term);
+        } else {
+            SimpleEngine::add_resize(&mut negative, &term);
+        }
+        // power has error below 4/3 and division adds one more unit
+        error += 3;
+        power = div(&power, &n2);
+        k += 1;
+    }
+    (sub(&positive, &negative), error)
+}
+
+/// Returns `pi * 2^scale` and its error.
+fn pi_fixed<D>(scale: usize) -> (VecBigNum<D>, u64)
+where
+    D: UnsignedNumDigit,
+{
+    // Machin's formula: pi = 16 atan(1/5) - 4 atan(1/239)
+    let guard = guard_bits(scale);
+    let (mut a, a_error) = arctan_inv::<D>(5, false, scale + guard);
+    let (mut b, b_error) = arctan_inv::<D>(239, false, scale + guard);
+    shift_left(&mut a, 4);
+    shift_left(&mut b, 2);
+    let pi = sub(&a, &b);
+    let (pi, error) = drop_bits(pi.into(), guard, 16 * a_error + 4 * b_error);
+    (pi.into_parts().0, error)
+}
+
+/// Returns `ln(2) * 2^scale` and its error.
+fn ln2_fixed<D>(scale: usize) -> (VecBigNum<D>, u64)
+where
+    D: UnsignedNumDigit,
+{
+    // ln(2) = 2 atanh(1/3)
+    let guard = guard_bits(scale);
+    let (ln2, error) = arctan_inv::<D>(3, true, scale + guard + 1);
+    let (ln2, error) = drop_bits(ln2.into(), guard, error);
+    (ln2.into_parts().0, error)
+}
+
+/// Approximates `exp(x)` for fixed point `x` with `scale` fractional bits and given error.
+fn exp_approx<D>(x: &SignedVecBigNum<D>, scale: usize, error: u64) -> Approx<D>
+where
+    D: UnsignedNumDigit,
+{
+    let int_bits = x.magnitude().bit_len().saturating_sub(scale);
+    assert!(int_bits < 62, "Exponent overflow");
+
+    // x = n ln(2) + r, where 0 <= r < ln(2), is computed with extra bits, so error of n ln(2) stays small
+    let extra = int_bits + 2;
+    let (ln2, ln2_error) = ln2_fixed::<D>(scale + extra);
+    let mut scaled = x.magnitude().clone();
+    shift_left(&mut scaled, extra as u64);
+    let (n, mut r) = div_rem(&scaled, &ln2);
+    let mut n = to_u64(&n) as i64;
+    if x.is_negative() {
+        n = -n;
+        if !r.is_zero() {
+            n -= 1;
+            r = sub(&ln2, &r);
+        }
+    }
+    shift_right(&mut r, extra as u64);
+    let r_error = error + ln2_error + 2;
+
+    // exp(r) = exp(r / 2^halvings)^(2^halvings), where the series for small argument converges fast
+    let halvings = bit_len(scale as u64);
+    let work = scale + halvings;
+    let mut sum = pow2(work);
+    let mut term = sum.clone();
+    let mut j = 1;
+    while !term.is_zero() {
+        term = div_u64(&mul_fixed(&term, &r, work), j);
+        SimpleEngine::add_resize(&mut sum, &term);
+        j += 1;
+    }
+    // each term adds two units and error of the argument grows at most twice
+    let error = 2 * r_error + 2 * j + 2;
+
+    for _ in 0..halvings {
+        sum = mul_fixed(&sum, &sum, work);
+    }
+    // each squaring doubles the relative error and the result is below two
+    Approx {
+        value: sum.into(),
+        exponent: n - work as i64,
+        error: (error + 1) << (halvings + 2),
+    }
+}
+
+/// Approximates `ln(x)` for positive `x` as fixed point number with `scale` fractional bits.
+fn ln_approx<D>(x: &BigFloat<D>, scale: usize) -> (SignedVecBigNum<D>, u64)
+where
+    D: UnsignedNumDigit,
+{
+    let guard = guard_bits(scale);
+    let work = scale + guard;
+
+    // x = f * 2^k, where 2/3 <= f <= 4/3
+    let mantissa = x.mantissa().into_parts().0;
+    let bits = mantissa.bit_len();
+    let mut k = x.exponent() + bits as i64 - 1;
+    let mut shift = work as i64 - bits as i64 + 1;
+    if SimpleEngine::compare(&mul(&mantissa, &from_u64(3)), &pow2(bits + 1)) == Ordering::Greater {
+        k += 1;
+        shift -= 1;
+    }
+    let mut f = mantissa;
+    if shift >= 0 {
+        shift_left(&mut f, shift as u64);
+    } else {
+        shift_right(&mut f, shift.unsigned_abs());
+    }
+
+    // ln(f) = 2 atanh(z), where z = (f - 1) / (f + 1) and |z| <= 1/5
+    let one = pow2(work);
+    let minus = SimpleEngine::compare(&f, &one) == Ordering::Less;
+    let mut numerator = if minus { sub(&one, &f) } else { sub(&f, &one) };
+    shift_left(&mut numerator, work as u64);
+    let z = div(&numerator, &add(&f, &one));
+    let z2 = mul_fixed(&z, &z, work);
+    let mut sum = z.clone();
+    let mut power = z;
+    let mut j = 1;
+    while !power.is_zero() {
+        power = mul_fixed(&power, &z2, work);
+        SimpleEngine::add_resize(&mut sum, &div_u64(&power, 2 * j + 1));
+        j += 1;
+    }
+    // each term adds two units, z and the rest of the series add two units each, then the sum is doubled
+    let mut error = 2 * (2 * j + 4);
+    shift_left(&mut sum, 1);
+    let mut res = SignedVecBigNum::from_parts(sum, minus);
+
+    if k != 0 {
+        let k_bits = bit_len(k.unsigned_abs());
+        let (ln2, ln2_error) = ln2_fixed::<D>(work + k_bits);
+        let mut multiple = mul(&ln2, &from_u64(k.unsigned_abs()));
+        shift_right(&mut multiple, k_bits as u64);
+        Engine::add_resize(&mut res, &SignedVecBigNum::from_parts(multiple, k < 0));
+        error += ln2_error + 1;
+    }
+    drop_bits(res, guard, error)
+}
+
+/// Approximates `atan(x)` as fixed point number with `scale` fractional bits.
+fn atan_approx<D>(x: &BigFloat<D>, scale: usize) -> (SignedVecBigNum<D>, u64)
+where
+    D: UnsignedNumDigit,
+{
+    // atan(y) = 2 atan(y / (1 + sqrt(1 + y^2))), so argument is halved several times before using the series
+    let halvings = bit_len(scale as u64);
+    let guard = guard_bits(scale) + halvings;
+    let work = scale + guard;
+    let one = pow2(work);
+
+    // atan(|x|) = pi/2 - atan(1/|x|) keeps the argument below one
+    let mantissa = x.mantissa().into_parts().0;
+    let inverted = top(x) > 1 || (top(x) == 1 && !mantissa.is_one());
+    let mut y = if inverted {
+        let shift = work as i64 - x.exponent();
+        if shift < 0 {
+            VecBigNum::new_zeroed()
+        } else {
+            div(&pow2(shift as usize), &mantissa)
+        }
+    } else {
+        to_fixed(x, work)
+    };
+    for _ in 0..halvings {
+        let root = isqrt(&add(&mul(&one, &one), &mul(&y, &y)));
+        shift_left(&mut y, work as u64);
+        y = div(&y, &add(&one, &root));
+    }
+
+    let y2 = mul_fixed(&y, &y, work);
+    let mut positive = y.clone();
+    let mut negative = VecBigNum::new_zeroed();
+    let mut power = y;
+    let mut j = 1;
+    while !power.is_zero() {
+        power = mul_fixed(&power, &y2, work);
+        let term = div_u64(&power, 2 * j + 1);
+        if j % 2 == 1 {
+            SimpleEngine::add_resize(&mut negative, &term);
+        } else {
+            SimpleEngine::add_resize(&mut positive, &term);
+        }
+        j += 1;
+    }
+    // halving keeps error of y below four units and each term adds at most three more
+    let mut error = (3 * j + 6) << halvings;
+    shift_left(&mut positive, halvings as u64);
+    shift_left(&mut negative, halvings as u64);
+    let mut res = sub_signed(positive, negative);
+
+    if inverted {
+        let (half_pi, pi_error) = pi_fixed::<D>(work - 1);
+        res = sub_signed(half_pi, res.into_parts().0);
+        error += pi_error;
+    }
+    let (value, minus) = res.into_parts();
+    drop_bits(
+        SignedVecBigNum::from_parts(value, minus != x.is_negative()),
+        guard,
+        error,
+    )
+}
+
+/// Approximates `sin(x)` or `cos(x)` as fixed point number with `scale` fractional bits.
+fn sin_cos_approx<D>(x: &BigFloat<D>, scale: usize, cosine: bool) -> (SignedVecBigNum<D>, u64)
+where
+    D: UnsignedNumDigit,
+{
+    let guard = guard_bits(scale);
+    let work = scale + guard;
+
+    // |x| = q pi/2 + r, where |r| <= pi/4, is computed with extra bits, so error of q pi/2 stays small
+    let extra = top(x).max(0) as usize + 2;
+    let (half_pi, pi_error) = pi_fixed::<D>(work + extra - 1);
+    let mut quarter_pi = half_pi.clone();
+    shift_right(&mut quarter_pi, 1);
+    let (q, rem) = div_rem(&add(&to_fixed(x, work + extra), &quarter_pi), &half_pi);
+    let r_minus = SimpleEngine::compare(&rem, &quarter_pi) == Ordering::Less;
+    let mut r = if r_minus {
+        sub(&quarter_pi, &rem)
+    } else {
+        sub(&rem, &quarter_pi)
+    };
+    shift_right(&mut r, extra as u64);
+    let r_error = pi_error + 2;
+
+    // sin(q pi/2 + r) and cos(q pi/2 + r) are equal to one of +-sin(r) and +-cos(r)
+    let quadrant = q.test_bit(0) as usize + 2 * q.test_bit(1) as usize + cosine as usize;
+    let use_cos = quadrant % 2 == 1;
+    let mut minus = quadrant % 4 >= 2;
+    if !use_cos && r_minus {
+        minus = !minus;
+    }
+    if !cosine && x.is_negative() {
+        minus = !minus;
+    }
+
+    let r2 = mul_fixed(&r, &r, work);
+    let mut term = if use_cos { pow2(work) } else { r };
+    let mut positive = term.clone();
+    let mut negative = VecBigNum::new_zeroed();
+    let mut j = 1;
+    while !term.is_zero() {
+        let divisor = if use_cos {
+            (2 * j - 1) * (2 * j)
+        } else {
+            (2 * j) * (2 * j + 1)
+        };
+        term = div_u64(&mul_fixed(&term, &r2, work), divisor);
+        if j % 2 == 1 {
+            SimpleEngine::add_resize(&mut negative, &term);
+        } else {
+            SimpleEngine::add_resize(&mut positive, &term);
+        }
+        j += 1;
+    }
+    // each term has error below twice the error of r plus four units
+    let error = (j + 1) * (2 * r_error + 4);
+
+    let (value, value_minus) = sub_signed(positive, negative).into_parts();
+    drop_bits(
+        SignedVecBigNum::from_parts(value, value_minus != minus),
+        guard,
+        error,
+    )
+}
+
+/// Approximates `x^y` for positive `x`.
+fn pow_approx<D>(x: &BigFloat<D>, y: &BigFloat<D>, scale: usize) -> Approx<D>
+where
+    D: UnsignedNumDigit,
+{
+    // x^y = exp(y ln(x)), where the logarithm is computed with more bits for bigger y
+    let (y_mantissa, y_minus) = y.mantissa().into_parts();
+    let y_bits = y_mantissa.bit_len();
+    let ln_scale = (scale as i64 + y_bits as i64 + 2 + y.exponent()).max(0);
+    let (ln, ln_error) = ln_approx(x, ln_scale as usize);
+    let (ln, ln_minus) = ln.into_parts();
+
+    // product has ln_scale - y.exponent() fractional bits and error below ln_error * 2^y_bits
+    let shift = (ln_scale - y.exponent() - scale as i64) as usize;
+    let mut z = mul(&ln, &y_mantissa);
+    shift_right(&mut z, shift as u64);
+    exp_approx(
+        &SignedVecBigNum::from_parts(z, ln_minus != y_minus),
+        scale,
+        ln_error + 1,
+    )
+}
+
+/// Approximation `value * 2^exponent` of the exact result, which differs from it by less than
+/// `error * 2^exponent`.
+struct Approx<D> {
+    value: SignedVecBigNum<D>,
+    exponent: i64,
+    error: u64,
+}
+
+impl<D> Approx<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn fixed((value, error): (SignedVecBigNum<D>, u64), scale: usize) -> Self {
+        Self {
+            value,
+            exponent: -(scale as i64),
+            error,
+        }
+    }
+}
+
+/// Rounds result to `precision` bits using approximations with growing number of bits, until the whole
+/// range of possible exact values rounds to the same number.
+///
+/// It never ends for exact results, which are representable or lie halfway between representable numbers,
+/// so these have to be handled before.
+fn round_approx<D, F>(precision: usize, mode: RoundingMode, approx: F) -> BigFloat<D>
+where
+    D: UnsignedNumDigit,
+    F: Fn(usize) -> Approx<D>,
+{
+    assert!(precision > 0, "Precision must be at least one bit");
+    let mut bits = precision + 32;
+    loop {
+        let Approx {
+            value,
+            exponent,
+            error,
+        } = approx(bits);
+        let (value, minus) = value.into_parts();
+        let error = from_u64(error);
+        if SimpleEngine::compare(&value, &error) == Ordering::Greater {
+            let lower = SignedVecBigNum::from_parts(sub(&value, &error), minus);
+            let upper = SignedVecBigNum::from_parts(add(&value, &error), minus);
+            let lower = BigFloat::from_parts(&lower, exponent, precision, mode);
+            if lower == BigFloat::from_parts(&upper, exponent, precision, mode) {
+                return lower;
+            }
+        }
+
+        // when too few bits are correct, the missing ones are added on top of the usual growth
+        let correct = value.bit_len().saturating_sub(error.bit_len() + 1);
+        bits += (bits / 2).max((precision + 32).saturating_sub(correct));
+    }
+}
+
+/// Elementary functions and constants.
+///
+/// Results are correctly rounded to requested precision: they are computed with growing number of bits and
+/// tracked error bound, until the rounding is known.
+impl<D> BigFloat<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Returns pi rounded to `precision` bits.
+    pub fn pi(precision: usize, mode: RoundingMode) -> Self {
+        round_approx(precision, mode, |bits| {
+            let (pi, error) = pi_fixed(bits);
+            Approx::fixed((pi.into(), error), bits)
+        })
+    }
+
+    /// Returns Euler's number rounded to `precision` bits.
+    pub fn e(precision: usize, mode: RoundingMode) -> Self {
+        Self::one(precision).exp(precision, mode)
+    }
+
+    /// Returns natural logarithm of two rounded to `precision` bits.
+    pub fn ln_2(precision: usize, mode: RoundingMode) -> Self {
+        round_approx(precision, mode, |bits| {
+            let (ln2, error) = ln2_fixed(bits);
+            Approx::fixed((ln2.into(), error), bits)
+        })
+    }
+
+    /// Returns `e^self` rounded to `precision` bits.
+    ///
+    /// # Panic
+    /// Panics when result's exponent does not fit in `i64`.
+    pub fn exp(&self, precision: usize, mode: RoundingMode) -> Self {
+        if self.is_zero() {
+            return Self::one(precision);
+        }
+        round_approx(precision, mode, |bits| {
+            let x = SignedVecBigNum::from_parts(to_fixed(self, bits), self.is_negative());
+            exp_approx(&x, bits, 1)
+        })
+    }
+
+    /// Returns natural logarithm rounded to `precision` bits.
+    ///
+    /// # Panic
+    /// Panics when number is not positive.
+    pub fn ln(&self, precision: usize, mode: RoundingMode) -> Self {
+        assert!(
+            !self.is_zero() && !self.is_negative(),
+            "Logarithm of non positive number"
+        );
+        if *self == Self::one(precision) {
+            return Self::zero(precision);
+        }
+        round_approx(precision, mode, |bits| {
+            Approx::fixed(ln_approx(self, bits), bits)
+        })
+    }
+
+    /// Returns sine rounded to `precision` bits.
+    pub fn sin(&self, precision: usize, mode: RoundingMode) -> Self {
+        if self.is_zero() {
+            return Self::zero(precision);
+        }
+        // sin(x) is close to x for small x, so more fractional bits are needed
+        let extra = (-top(self)).max(0) as usize;
+        round_approx(precision, mode, |bits| {
+            Approx::fixed(sin_cos_approx(self, bits + extra, false), bits + extra)
+        })
+    }
+
+    /// Returns cosine rounded to `precision` bits.
+    pub fn cos(&self, precision: usize, mode: RoundingMode) -> Self {
+        if self.is_zero() {
+            return Self::one(precision);
+        }
+        round_approx(precision, mode, |bits| {
+            Approx::fixed(sin_cos_approx(self, bits, true), bits)
+        })
+    }
+
+    /// Returns arcus tangent rounded to `precision` bits.
+    pub fn atan(&self, precision: usize, mode: RoundingMode) -> Self {
+        if self.is_zero() {
+            return Self::zero(precision);
+        }
+        // atan(x) is close to x for small x, so more fractional bits are needed
+        let extra = (-top(self)).max(0) as usize;
+        round_approx(precision, mode, |bits| {
+            Approx::fixed(atan_approx(self, bits + extra), bits + extra)
+        })
+    }
+
+    /// Returns `self^exponent` rounded to `precision` bits. Zero to the power of zero is one.
+    ///
+    /// # Panic
+    /// Panics when zero is raised to negative power, negative number is raised to non integer power or when
+    /// result's exponent does not fit in `i64`.
+    pub fn pow(&self, exponent: &Self, precision: usize, mode: RoundingMode) -> Self {
+        let one = Self::one(precision);
+        if exponent.is_zero() || *self == one {
+            return one;
+        }
+        if self.is_zero() {
+            assert!(!exponent.is_negative(), "Division by zero");
+            return Self::zero(precision);
+        }
+
+        let mut base = self.clone();
+        let mut exponent = exponent.clone();
+        if exponent.exponent() < 0 {
+            assert!(
+                !self.is_negative(),
+                "Power of negative number to non integer exponent"
+            );
+            // x^(m/2^k) with odd m is rational only when x is a square, so roots are taken while possible
+            while exponent.exponent() < 0 {
+                let mantissa = base.mantissa().into_parts().0;
+                let root = isqrt(&mantissa);
+                if base.exponent() % 2 != 0 || mul(&root, &root) != mantissa {
+                    break;
+                }
+                let root_bits = root.bit_len();
+                base = Self::from_parts(
+                    &root.into(),
+                    base.exponent() / 2,
+                    root_bits,
+                    RoundingMode::NearestEven,
+                );
+                exponent = Self::from_parts(
+                    &exponent.mantissa(),
+                    exponent.exponent() + 1,
+                    exponent.precision(),
+                    RoundingMode::NearestEven,
+                );
+            }
+        }
+
+        let mantissa = base.mantissa().into_parts().0;
+        let minus = base.is_negative() && exponent.exponent() == 0;
+        if exponent.exponent() >= 0 {
+            // integer powers are computed exactly, unless they are too long to be representable
+            if mantissa.is_one() {
+                assert!(top(&exponent) < 64, "Exponent overflow");
+                let n = to_fixed(&exponent, 0);
+                let n = if exponent.is_negative() {
+                    -(to_u64(&n) as i64)
+                } else {
+                    to_u64(&n) as i64
+                };
+                let power = base.exponent().checked_mul(n).expect("Exponent overflow");
+                return Self::from_parts(
+                    &SignedVecBigNum::from_parts(from_u64(1), minus),
+                    power,
+                    precision,
+                    mode,
+                );
+            }
+            if top(&exponent) < 64 {
+                let n = to_u64(&to_fixed(&exponent, 0));
+                if (n as u128) * (mantissa.bit_len() as u128 - 1) <= precision as u128 + 1 {
+                    let power = pow_u64(&mantissa, n);
+                    let power_bits = power.bit_len();
+                    let power = Self::from_parts(
+                        &SignedVecBigNum::from_parts(power, minus),
+                        base.exponent()
+                            .checked_mul(n as i64)
+                            .expect("Exponent overflow"),
+                        power_bits,
+                        RoundingMode::NearestEven,
+                    );
+                    return if exponent.is_negative() {
+                        one.div_with(&power, precision, mode)
+                    } else {
+                        power.with_precision(precision, mode)
+                    };
+                }
+            }
+        }
+
+        let base = base.abs();
+        round_approx(precision, mode, |bits| {
+            let mut res = pow_approx(&base, &exponent, bits);
+            if minus {
+                res.value = SignedVecBigNum::from_parts(res.value.into_parts().0, true);
+            }
+            res
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::iutil::xorshift;
+
+    const MODES: [RoundingMode; 5] = [
+        RoundingMode::NearestEven,
+        RoundingMode::NearestAway,
+        RoundingMode::TowardZero,
+        RoundingMode::Up,
+        RoundingMode::Down,
+    ];
+
+    fn float(v: f64) -> BigFloat<u32> {
+        BigFloat::from_f64(v).unwrap()
+    }
+
+    /// Maps doubles to integers, which are consecutive for consecutive doubles.
+    fn ordered(v: f64) -> i64 {
+        let bits = v.to_bits() as i64;
+        if bits < 0 {
+            i64::MIN - bits
+        } else {
+            bits
+        }
+    }
+
+    fn from_digits(digits: Vec<u64>, exponent: i64) -> BigFloat<u64> {
+        let precision = digits.len() * 64;
+        BigFloat::from_parts(
+            &SignedVecBigNum::from(VecBigNum::from(digits)),
+            exponent,
+            precision,
+            RoundingMode::NearestEven,
+        )
+    }
+
+    #[test]
+    fn test_constants() {
+        let pi = from_digits(
+            vec![
+                0x020bbea6_3b139b22,
+                0x29024e08_8a67cc74,
+                0xc4c6628b_80dc1cd1,
+                0xc90fdaa2_2168c234,
+            ],
+            2 - 256,
+        );
+        assert_eq!(BigFloat::pi(256, RoundingMode::TowardZero), pi);
+        assert_eq!(BigFloat::pi(256, RoundingMode::NearestEven), pi);
+
+        let e = from_digits(
+            vec![
+                0xa9e13641_146433fb,
+                0xd8b9c583_ce2d3695,
+                0xafdc5620_273d3cf1,
+                0xadf85458_a2bb4a9a,
+            ],
+            2 - 256,
+        );
+        assert_eq!(BigFloat::e(256, RoundingMode::TowardZero), e);
+
+        let ln2 = from_digits(vec![0xc9e3b398_03f2f6af, 0xb17217f7_d1cf79ab], -128);
+        assert_eq!(BigFloat::ln_2(128, RoundingMode::TowardZero), ln2);
+
+        // constants agree with functions
+        for &mode in &MODES {
+            let one = BigFloat::<u32>::one(300);
+            let four = BigFloat::from_f64(4.0).unwrap();
+            assert_eq!(&one.atan(300, mode) * &four, BigFloat::pi(300, mode));
+            assert_eq!(one.exp(300, mode), BigFloat::e(300, mode));
+            assert_eq!(float(2.0).ln(300, mode), BigFloat::ln_2(300, mode));
+        }
+    }
+
+    #[test]
+    fn test_matches_f64() {
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut random = |min: f64, max: f64| {
+            min + (max - min) * (xorshift(&mut state) >> 11) as f64 / (1u64 << 53) as f64
+        };
+
+        type Function = fn(&BigFloat<u32>, &BigFloat<u32>, RoundingMode) -> BigFloat<u32>;
+        type Expected = fn(f64, f64) -> f64;
+        let functions: [(Function, Expected); 6] = [
+            (|x, _, mode| x.exp(53, mode), |x, _| x.exp()),
+            (|x, _, mode| x.abs().ln(53, mode), |x, _| x.abs().ln()),
+            (|x, _, mode| x.sin(53, mode), |x, _| x.sin()),
+            (|x, _, mode| x.cos(53, mode), |x, _| x.cos()),
+            (|x, _, mode| x.atan(53, mode), |x, _| x.atan()),
+            (
+                |x, y, mode| x.abs().pow(y, 53, mode),
+                |x, y| x.abs().powf(y),
+            ),
+        ];
+        for _ in 0..100 {
+            let x = random(-20.0, 20.0);
+            let y = random(-10.0, 10.0);
+            for &(function, expected) in &functions {
+                let nearest = function(&float(x), &float(y), RoundingMode::NearestEven).to_f64();
+                let down = function(&float(x), &float(y), RoundingMode::Down).to_f64();
+                let up = function(&float(x), &float(y), RoundingMode::Up).to_f64();
+                let expected = expected(x, y);
+                assert!(
+                    (ordered(nearest) - ordered(expected)).abs() <= 1,
+                    "{} {}",
+                    x,
+                    y
+                );
+                assert_eq!(ordered(up) - ordered(down), 1, "{} {}", x, y);
+                assert!(nearest == up || nearest == down);
+            }
+        }
+    }
+
+    #[test]
+    fn test_exact_results() {
+        for &mode in &MODES {
+            assert_eq!(float(0.0).exp(10, mode), float(1.0));
+            assert_eq!(float(1.0).ln(10, mode), float(0.0));
+            assert_eq!(float(0.0).sin(10, mode), float(0.0));
+            assert_eq!(float(0.0).cos(10, mode), float(1.0));
+            assert_eq!(float(0.0).atan(10, mode), float(0.0));
+
+            let pow = |x: f64, y: f64| float(x).pow(&float(y), 53, mode).to_f64();
+            assert_eq!(pow(2.0, 10.0), 1024.0);
+            assert_eq!(pow(-2.0, -3.0), -0.125);
+            assert_eq!(pow(3.0, 5.0), 243.0);
+            assert_eq!(pow(-3.0, 5.0), -243.0);
+            assert_eq!(pow(4.0, 0.5), 2.0);
+            assert_eq!(pow(16.0, -0.25), 0.5);
+            assert_eq!(pow(9.0, 1.5), 27.0);
+            assert_eq!(pow(0.0, 2.0), 0.0);
+            assert_eq!(pow(0.0, 0.0), 1.0);
+            assert_eq!(pow(1.0, 0.123), 1.0);
+        }
+        let third = float(3.0).pow(&float(-1.0), 53, RoundingMode::NearestEven);
+        assert_eq!(third.to_f64(), 1.0 / 3.0);
+        let root = float(2.0).pow(&float(0.5), 53, RoundingMode::NearestEven);
+        assert_eq!(root.to_f64(), 2f64.sqrt());
+    }
+
+    #[test]
+    fn test_identities() {
+        let mode = RoundingMode::NearestEven;
+        let x = float(0.7).with_precision(200, mode);
+        let sin = x.sin(400, mode);
+        let cos = x.cos(400, mode);
+        let one = &(&sin * &sin) + &(&cos * &cos);
+        assert_eq!(one.with_precision(390, mode), BigFloat::one(390));
+
+        let big = float(1e22);
+        assert_eq!(big.sin(53, mode).to_f64(), -0.8522008497671888);
+        assert_eq!(float(1e-300).sin(53, mode), float(1e-300));
+        assert_eq!(float(1e-300).atan(53, mode), float(1e-300));
+        assert_eq!(
+            float(1e300).atan(53, mode).to_f64(),
+            core::f64::consts::FRAC_PI_2
+        );
+
+        let y = float(-123.456).exp(300, mode);
+        assert_eq!(y.ln(200, mode), float(-123.456));
+
+        // ln(1 + 2^-100) is just below 2^-100
+        let x = BigFloat::<u32>::from_parts(
+            &SignedVecBigNum::from(add(&pow2(100), &from_u64(1))),
+            -100,
+            101,
+            mode,
+        );
+        assert_eq!(
+            x.ln(53, mode),
+            BigFloat::from_parts(&SignedVecBigNum::from(from_u64(1)), -100, 53, mode)
+        );
+        assert_eq!(
+            x.ln(53, RoundingMode::Down),
+            BigFloat::from_parts(
+                &SignedVecBigNum::from(sub(&pow2(53), &from_u64(1))),
+                -153,
+                53,
+                mode
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ln_of_negative() {
+        float(-1.0).ln(10, RoundingMode::NearestEven);
+    }
+}
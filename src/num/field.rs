@@ -0,0 +1,477 @@
+use crate::num::{ArrayBigNum, BigNum, BigNumBits, VecBigNum};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::*;
+
+/// Returns `-m^-1 mod 2^64` for odd `m`.
+const fn montgomery_inv(m: u64) -> u64 {
+    assert!(m & 1 == 1, "Modulus must be odd");
+
+    // Newton iteration doubles amount of correct low bits and m * m = 1 mod 8
+    let mut inv = m;
+    let mut correct_bits = 3;
+    while correct_bits < 64 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(m.wrapping_mul(inv)));
+        correct_bits *= 2;
+    }
+    inv.wrapping_neg()
+}
+
+const fn is_less<const N: usize>(lhs: &[u64; N], rhs: &[u64; N]) -> bool {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if lhs[i] != rhs[i] {
+            return lhs[i] < rhs[i];
+        }
+    }
+    false
+}
+
+/// Adds two numbers. Returns sum and carry.
+const fn add_digits<const N: usize>(lhs: &[u64; N], rhs: &[u64; N]) -> ([u64; N], bool) {
+    let mut res = [0; N];
+    let mut carry = false;
+    let mut i = 0;
+    while i < N {
+        let (sum, c1) = lhs[i].overflowing_add(rhs[i]);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        res[i] = sum;
+        carry = c1 || c2;
+        i += 1;
+    }
+    (res, carry)
+}
+
+/// Subtracts two numbers. Returns difference and borrow.
+const fn sub_digits<const N: usize>(lhs: &[u64; N], rhs: &[u64; N]) -> ([u64; N], bool) {
+    let mut res = [0; N];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < N {
+        let (diff, b1) = lhs[i].overflowing_sub(rhs[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        res[i] = diff;
+        borrow = b1 || b2;
+        i += 1;
+    }
+    (res, borrow)
+}
+
+const fn add_mod<const N: usize>(lhs: &[u64; N], rhs: &[u64; N], m: &[u64; N]) -> [u64; N] {
+    let (sum, carry) = add_digits(lhs, rhs);
+    if carry || !is_less(&sum, m) {
+        sub_digits(&sum, m).0
+    } else {
+        sum
+    }
+}
+
+const fn sub_mod<const N: usize>(lhs: &[u64; N], rhs: &[u64; N], m: &[u64; N]) -> [u64; N] {
+    let (diff, borrow) = sub_digits(lhs, rhs);
+    if borrow {
+        add_digits(&diff, m).0
+    } else {
+        diff
+    }
+}
+
+/// Returns `2^exp mod m` for `m > 1`.
+const fn pow2_mod<const N: usize>(m: &[u64; N], exp: usize) -> [u64; N] {
+    let mut res = [0; N];
+    res[0] = 1;
+    let mut i = 0;
+    while i < exp {
+        res = add_mod(&res, &res, m);
+        i += 1;
+    }
+    res
+}
+
+/// Computes `lhs * rhs * 2^(-64 * N) mod m`, for `lhs * rhs < m * 2^(64 * N)`.
+/// Montgomery multiplication with coarsely integrated operand scanning is used.
+const fn montgomery_mul<const N: usize>(
+    lhs: &[u64; N],
+    rhs: &[u64; N],
+    m: &[u64; N],
+    inv: u64,
+) -> [u64; N] {
+    // t has two more digits: hi and top
+    let mut t = [0u64; N];
+    let mut hi = 0u64;
+    let mut i = 0;
+    while i < N {
+        // t += lhs * rhs[i]
+        let mut carry = 0u64;
+        let mut j = 0;
+        while j < N {
+            let acc = t[j] as u128 + lhs[j] as u128 * rhs[i] as u128 + carry as u128;
+            t[j] = acc as u64;
+            carry = (acc >> 64) as u64;
+            j += 1;
+        }
+        let acc = hi as u128 + carry as u128;
+        hi = acc as u64;
+        let top = (acc >> 64) as u64;
+
+        // t = (t + u * m) / 2^64, where u is chosen so the lowest digit becomes zero
+        let u = t[0].wrapping_mul(inv);
+        let acc = t[0] as u128 + u as u128 * m[0] as u128;
+        let mut carry = (acc >> 64) as u64;
+        let mut j = 1;
+        while j < N {
+            let acc = t[j] as u128 + u as u128 * m[j] as u128 + carry as u128;
+            t[j - 1] = acc as u64;
+            carry = (acc >> 64) as u64;
+            j += 1;
+        }
+        let acc = hi as u128 + carry as u128;
+        t[N - 1] = acc as u64;
+        hi = top + (acc >> 64) as u64;
+        i += 1;
+    }
+
+    // result is smaller than 2m
+    if hi != 0 || !is_less(&t, m) {
+        sub_digits(&t, m).0
+    } else {
+        t
+    }
+}
+
+/// Prime modulus of field `Fp`, which is defined by type implementing this trait.
+///
+/// Only `MODULUS` has to be provided, Montgomery constants are computed at compile time from it.
+/// Using them fails compilation when modulus is even.
+///
+/// Modulus has to be prime bigger than two, otherwise division and inversion give wrong results.
+pub trait FieldModulus<const N: usize>: 'static {
+    /// Little endian digits of modulus.
+    const MODULUS: [u64; N];
+
+    /// `-MODULUS^-1 mod 2^64`.
+    const INV: u64 = montgomery_inv(Self::MODULUS[0]);
+
+    /// `R mod MODULUS` for `R = 2^(64 * N)`, which is one in Montgomery form.
+    const R: [u64; N] = pow2_mod(&Self::MODULUS, 64 * N);
+
+    /// `R^2 mod MODULUS`, which converts numbers into Montgomery form.
+    const R2: [u64; N] = pow2_mod(&Self::MODULUS, 128 * N);
+}
+
+/// Element of prime field defined by `P`, which is stored in `N` 64 bit digits.
+///
+/// Elements are kept in Montgomery form and all arithmetic is done on fixed size arrays,
+/// so it does not allocate. Elements of different fields have different types, so they can't be mixed.
+pub struct Fp<P, const N: usize> {
+    /// Value in Montgomery form, always smaller than modulus.
+    value: [u64; N],
+    field: PhantomData<P>,
+}
+
+impl<P, const N: usize> Fp<P, N>
+where
+    P: FieldModulus<N>,
+{
+    pub const ZERO: Self = Self::from_montgomery([0; N]);
+    pub const ONE: Self = Self::from_montgomery(P::R);
+
+    const fn from_montgomery(value: [u64; N]) -> Self {
+        Self {
+            value,
+            field: PhantomData,
+        }
+    }
+
+    /// Creates element from little endian digits of number.
+    /// Number may be bigger than modulus, then it's reduced.
+    pub const fn from_raw(digits: [u64; N]) -> Self {
+        // digits * R2 < 2^(64 * N) * MODULUS, so reduction works for any number
+        Self::from_montgomery(montgomery_mul(&digits, &P::R2, &P::MODULUS, P::INV))
+    }
+
+    /// Creates element from primitive integer.
+    pub const fn from_u64(v: u64) -> Self {
+        let mut digits = [0; N];
+        digits[0] = v;
+        Self::from_raw(digits)
+    }
+
+    /// Returns little endian digits of value, which is smaller than modulus.
+    pub const fn to_raw(&self) -> [u64; N] {
+        let mut one = [0; N];
+        one[0] = 1;
+        montgomery_mul(&self.value, &one, &P::MODULUS, P::INV)
+    }
+
+    /// Returns value of element as bignum.
+    pub fn to_big_num(&self) -> ArrayBigNum<u64, N> {
+        ArrayBigNum::from(self.to_raw())
+    }
+
+    /// Returns modulus of field.
+    pub fn modulus() -> ArrayBigNum<u64, N> {
+        ArrayBigNum::from(P::MODULUS)
+    }
+
+    /// Returns true if element is zero.
+    pub const fn is_zero(&self) -> bool {
+        let mut i = 0;
+        while i < N {
+            if self.value[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Returns `self * self`.
+    pub const fn square(&self) -> Self {
+        Self::from_montgomery(montgomery_mul(
+            &self.value,
+            &self.value,
+            &P::MODULUS,
+            P::INV,
+        ))
+    }
+
+    /// Raises element to power `exp`.
+    /// Zero raised to zero is one.
+    pub fn pow(&self, exp: &impl BigNum<Digit = u64>) -> Self {
+        let mut res = Self::ONE;
+        for bit in (0..exp.bit_len()).rev() {
+            res = res.square();
+            if exp.test_bit(bit) {
+                res *= *self;
+            }
+        }
+        res
+    }
+
+    /// Returns multiplicative inverse of element, which is computed as `self^(p - 2)`.
+    /// Returns `None` for zero.
+    pub fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let mut two = [0; N];
+        two[0] = 2;
+        let (exp, _) = sub_digits(&P::MODULUS, &two);
+        Some(self.pow(&ArrayBigNum::from(exp)))
+    }
+}
+
+impl<P, const N: usize> From<ArrayBigNum<u64, N>> for Fp<P, N>
+where
+    P: FieldModulus<N>,
+{
+    /// Creates element from number, which is reduced when it's bigger than modulus.
+    fn from(num: ArrayBigNum<u64, N>) -> Self {
+        Self::from_raw(num.into_inner())
+    }
+}
+
+impl<P, const N: usize> Clone for Fp<P, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P, const N: usize> Copy for Fp<P, N> {}
+
+impl<P, const N: usize> PartialEq for Fp<P, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // Montgomery form is unique for each residue
+        self.value == other.value
+    }
+}
+
+impl<P, const N: usize> Eq for Fp<P, N> {}
+
+impl<P, const N: usize> Hash for Fp<P, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<P, const N: usize> Default for Fp<P, N>
+where
+    P: FieldModulus<N>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<P, const N: usize> fmt::Display for Fp<P, N>
+where
+    P: FieldModulus<N>,
+{
+    /// Formats value in decimal.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&VecBigNum::from(self.to_raw().to_vec()), f)
+    }
+}
+
+impl<P, const N: usize> fmt::Debug for Fp<P, N>
+where
+    P: FieldModulus<N>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fp({})", self)
+    }
+}
+
+macro_rules! derive_fp_op {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, |$lhs:ident, $rhs:ident| $body:expr) => {
+        impl<P, const N: usize> $trait for Fp<P, N>
+        where
+            P: FieldModulus<N>,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self {
+                let ($lhs, $rhs) = (self, rhs);
+                $body
+            }
+        }
+
+        impl<P, const N: usize> $assign_trait for Fp<P, N>
+        where
+            P: FieldModulus<N>,
+        {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $trait::$fn(*self, rhs);
+            }
+        }
+    };
+}
+
+derive_fp_op!(Add, add, AddAssign, add_assign, |lhs, rhs| {
+    Fp::from_montgomery(add_mod(&lhs.value, &rhs.value, &P::MODULUS))
+});
+derive_fp_op!(Sub, sub, SubAssign, sub_assign, |lhs, rhs| {
+    Fp::from_montgomery(sub_mod(&lhs.value, &rhs.value, &P::MODULUS))
+});
+derive_fp_op!(Mul, mul, MulAssign, mul_assign, |lhs, rhs| {
+    Fp::from_montgomery(montgomery_mul(&lhs.value, &rhs.value, &P::MODULUS, P::INV))
+});
+derive_fp_op!(Div, div, DivAssign, div_assign, |lhs, rhs| {
+    Mul::mul(lhs, rhs.inv().expect("Division by zero"))
+});
+
+impl<P, const N: usize> Neg for Fp<P, N>
+where
+    P: FieldModulus<N>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::iutil::xorshift;
+    use crate::num::{ModInt, Modulus};
+
+    struct Mersenne61;
+
+    impl FieldModulus<1> for Mersenne61 {
+        const MODULUS: [u64; 1] = [(1 << 61) - 1];
+    }
+
+    /// The biggest prime smaller than 2^64, so reduction carries out of the top digit.
+    struct Prime64;
+
+    impl FieldModulus<1> for Prime64 {
+        const MODULUS: [u64; 1] = [0xffff_ffff_ffff_ffc5];
+    }
+
+    /// 2^255 - 19
+    struct Prime25519;
+
+    impl FieldModulus<4> for Prime25519 {
+        const MODULUS: [u64; 4] = [
+            0xffff_ffff_ffff_ffed,
+            0xffff_ffff_ffff_ffff,
+            0xffff_ffff_ffff_ffff,
+            0x7fff_ffff_ffff_ffff,
+        ];
+    }
+
+    const FIVE: Fp<Mersenne61, 1> = Fp::from_u64(5);
+
+    fn check_field<P, const N: usize>()
+    where
+        P: FieldModulus<N>,
+    {
+        let modulus = Modulus::new(&Fp::<P, N>::modulus());
+        let mut state = 0x0123_4567_89ab_cdefu64;
+        let mut next = || {
+            let mut digits = [0; N];
+            for d in digits.iter_mut() {
+                *d = xorshift(&mut state);
+            }
+            digits
+        };
+
+        for _ in 0..50 {
+            let (a, b) = (next(), next());
+            let (x, y) = (Fp::<P, N>::from_raw(a), Fp::<P, N>::from_raw(b));
+            let (mx, my) = (
+                ModInt::new(&ArrayBigNum::from(a), &modulus),
+                ModInt::new(&ArrayBigNum::from(b), &modulus),
+            );
+
+            assert_eq!((x + y).to_big_num(), (&mx + &my).value());
+            assert_eq!((x - y).to_big_num(), (&mx - &my).value());
+            assert_eq!((x * y).to_big_num(), (&mx * &my).value());
+            assert_eq!((-x).to_big_num(), (-&mx).value());
+            assert_eq!(x.square(), x * x);
+            assert_eq!(x / y * y, x);
+            assert_eq!(Fp::from(x.to_big_num()), x);
+            assert_eq!(x.to_string(), mx.to_string());
+        }
+
+        let x = Fp::<P, N>::from_raw(next());
+        let mut p_minus_one = P::MODULUS;
+        p_minus_one[0] -= 1;
+        assert_eq!(x.pow(&ArrayBigNum::from(p_minus_one)), Fp::ONE);
+        assert_eq!(Fp::<P, N>::ZERO.inv(), None);
+        assert!((Fp::<P, N>::ONE - Fp::ONE).is_zero());
+    }
+
+    #[test]
+    fn test_fields() {
+        check_field::<Mersenne61, 1>();
+        check_field::<Prime64, 1>();
+        check_field::<Prime25519, 4>();
+    }
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(Mersenne61::R, [8]);
+        assert_eq!(Prime64::R, [59]);
+        assert_eq!(Prime25519::R2, [1444, 0, 0, 0]);
+        assert_eq!(FIVE.to_raw(), [5]);
+        assert_eq!(Fp::<Mersenne61, 1>::from_u64(u64::MAX).to_raw(), [7]);
+        assert_eq!(format!("{:?}", FIVE * FIVE), "Fp(25)");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_division_by_zero() {
+        let _ = FIVE / Fp::ZERO;
+    }
+}
@@ -0,0 +1,706 @@
+use crate::iutil::{from_u64, pow_u64};
+use crate::num::{
+    BigDecimal, BigFloat, BigNum, BigNumBits, DynamicBigNum, Int, ParseDecimalError, RoundingMode,
+    SignedVecBigNum, UnsignedNumDigit, VecBigNum,
+};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::*;
+use core::str::FromStr;
+
+fn magnitude<D, const N: usize>(num: &Int<D, N>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = VecBigNum::from(num.unsigned_abs().into_inner().to_vec());
+    res.normalize();
+    res
+}
+
+/// Returns `magnitude * 2^shift` rounded to integer: to the nearest one with ties to even when `nearest` is set,
+/// otherwise towards negative infinity, where `minus` is the sign of value.
+fn shift_rounded<D>(mut num: VecBigNum<D>, minus: bool, shift: i64, nearest: bool) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    if shift >= 0 {
+        SimpleEngine::shift_left_resize(&mut num, shift as u64);
+        return num;
+    }
+
+    let n = shift.unsigned_abs() as usize;
+    let round_bit = num.test_bit(n - 1);
+    let sticky = !num.is_zero() && num.trailing_zeros() < n - 1;
+    if n >= num.bit_len() {
+        num = VecBigNum::new_zeroed();
+    } else {
+        SimpleEngine::shift_right_u64(&mut num, n as u64);
+        num.normalize();
+    }
+
+    let increment = if nearest {
+        round_bit && (sticky || num.test_bit(0))
+    } else {
+        minus && (round_bit || sticky)
+    };
+    if increment {
+        SimpleEngine::add_resize(&mut num, &from_u64(1));
+    }
+    num
+}
+
+/// Converts integer with given magnitude and sign to `Int`. Result wraps and overflow flag is set, when it does not fit.
+fn to_int<D, const N: usize>(magnitude: &VecBigNum<D>, minus: bool) -> (Int<D, N>, bool)
+where
+    D: UnsignedNumDigit,
+{
+    let mut num = [D::ZERO; N];
+    for (i, d) in num.iter_mut().enumerate().take(magnitude.len()) {
+        *d = magnitude.get_digit(i);
+    }
+    let num = Int::from(num);
+
+    let bits = Int::<D, N>::BITS as usize;
+    let len = magnitude.bit_len();
+    if minus {
+        // -2^(BITS - 1) is the only negative number, which has magnitude with all bits
+        let overflow = len > bits || (len == bits && magnitude.trailing_zeros() != bits - 1);
+        (num.wrapping_neg(), overflow)
+    } else {
+        (num, len >= bits)
+    }
+}
+
+/// Binary fixed point number in Q format: integer `I` scaled by `2^-FRAC_BITS`.
+///
+/// For instance `Fixed<I256, 128>` has sign bit, 127 integer bits and 128 fractional bits, so it can be used as an
+/// accumulator of many products of narrower numbers without losing any bits.
+///
+/// Multiplication and division rescale results by shifting: product is rounded towards negative infinity
+/// and quotient towards zero. Operators behave just like for `Int`: they panic on overflow when debug assertions
+/// are enabled and wrap otherwise. Wrapping, checked, saturating and overflowing variants are available as methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed<I, const FRAC_BITS: u32> {
+    bits: I,
+}
+
+impl<D, const N: usize, const FRAC_BITS: u32> Fixed<Int<D, N>, FRAC_BITS>
+where
+    D: UnsignedNumDigit,
+{
+    /// Number of fractional bits.
+    pub const FRAC_BITS: u32 = FRAC_BITS;
+
+    /// Creates number from its bits, which are value multiplied by `2^FRAC_BITS`.
+    #[inline]
+    pub fn from_bits(bits: Int<D, N>) -> Self {
+        Self { bits }
+    }
+
+    /// Returns bits of this number, which are value multiplied by `2^FRAC_BITS`.
+    #[inline]
+    pub fn to_bits(self) -> Int<D, N> {
+        self.bits
+    }
+
+    /// Returns zero.
+    #[inline]
+    pub fn zero() -> Self {
+        Self::from_bits(Int::zero())
+    }
+
+    /// Returns one, which wraps when there are no integer bits.
+    #[inline]
+    pub fn one() -> Self {
+        Self::from_bits(Int::one().wrapping_shl(FRAC_BITS))
+    }
+
+    /// Returns the smallest positive number.
+    #[inline]
+    pub fn delta() -> Self {
+        Self::from_bits(Int::one())
+    }
+
+    /// Returns smallest value, which can be represented by this number.
+    #[inline]
+    pub fn min_value() -> Self {
+        Self::from_bits(Int::min_value())
+    }
+
+    /// Returns biggest value, which can be represented by this number.
+    #[inline]
+    pub fn max_value() -> Self {
+        Self::from_bits(Int::max_value())
+    }
+
+    /// Returns true if this number is smaller than zero.
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.bits.is_negative()
+    }
+
+    /// Creates number from integer. Returns `None` when it does not fit.
+    pub fn from_int(v: Int<D, N>) -> Option<Self> {
+        let minus = v.is_negative();
+        let num = shift_rounded(magnitude(&v), minus, FRAC_BITS as i64, false);
+        match to_int(&num, minus) {
+            (bits, false) => Some(Self::from_bits(bits)),
+            (_, true) => None,
+        }
+    }
+
+    /// Returns integer part of this number rounded towards negative infinity.
+    pub fn to_int_floor(self) -> Int<D, N> {
+        let minus = self.is_negative();
+        let num = shift_rounded(magnitude(&self.bits), minus, -(FRAC_BITS as i64), false);
+        to_int(&num, minus).0
+    }
+
+    /// Returns the nearest number to `v`, ties are rounded to even.
+    /// Returns `None` for infinities, NaN and values, which do not fit.
+    pub fn from_f64(v: f64) -> Option<Self> {
+        let v = BigFloat::<D>::from_f64(v)?;
+        let (num, minus) = v.mantissa().into_parts();
+        let num = shift_rounded(num, minus, v.exponent() + FRAC_BITS as i64, true);
+        match to_int(&num, minus) {
+            (bits, false) => Some(Self::from_bits(bits)),
+            (_, true) => None,
+        }
+    }
+
+    /// Returns the nearest `f64`, ties are rounded to even.
+    pub fn to_f64(self) -> f64 {
+        // value is converted exactly, so it's rounded only once, to precision of the result
+        let abs = magnitude(&self.bits);
+        let precision = abs.bit_len().max(1);
+        let num = SignedVecBigNum::from_parts(abs, self.is_negative());
+        BigFloat::from_parts(
+            &num,
+            -(FRAC_BITS as i64),
+            precision,
+            RoundingMode::NearestEven,
+        )
+        .to_f64()
+    }
+
+    /// Returns the nearest number to decimal `v`, ties are rounded to even. Returns `None` when it does not fit.
+    pub fn from_decimal(v: &BigDecimal<D>) -> Option<Self> {
+        let minus = v.is_negative();
+        let mut num = v.unscaled().magnitude().clone();
+        SimpleEngine::shift_left_resize(&mut num, FRAC_BITS as u64);
+        let den = pow_u64(&from_u64(10), v.scale() as u64);
+
+        let mut quot = VecBigNum::new_zeroed();
+        let mut rem = VecBigNum::new_zeroed();
+        SimpleEngine::div_rem_resize(&mut quot, &mut rem, &num, &den);
+        quot.normalize();
+        let mut twice = rem.clone();
+        SimpleEngine::add_resize(&mut twice, &rem);
+        match SimpleEngine::compare(&twice, &den) {
+            Ordering::Greater => SimpleEngine::add_resize(&mut quot, &from_u64(1)),
+            Ordering::Equal if quot.test_bit(0) => {
+                SimpleEngine::add_resize(&mut quot, &from_u64(1))
+            }
+            _ => {}
+        }
+
+        match to_int(&quot, minus) {
+            (bits, false) => Some(Self::from_bits(bits)),
+            (_, true) => None,
+        }
+    }
+
+    /// Returns exact decimal value of this number, which has `FRAC_BITS` fractional digits.
+    pub fn to_decimal(self) -> BigDecimal<D> {
+        // 2^-FRAC_BITS = 5^FRAC_BITS / 10^FRAC_BITS
+        let mut num = VecBigNum::new_zeroed();
+        SimpleEngine::mul_resize(
+            &mut num,
+            &magnitude(&self.bits),
+            &pow_u64(&from_u64(5), FRAC_BITS as u64),
+        );
+        BigDecimal::new(
+            SignedVecBigNum::from_parts(num, self.is_negative()),
+            FRAC_BITS as usize,
+        )
+    }
+
+    /// Converts number to the one with `M` digits and `G` fractional bits.
+    /// Dropped fractional bits are rounded towards negative infinity. Overflow flag is set when integer part
+    /// does not fit.
+    pub fn overflowing_convert<const M: usize, const G: u32>(self) -> (Fixed<Int<D, M>, G>, bool) {
+        let minus = self.is_negative();
+        let shift = G as i64 - FRAC_BITS as i64;
+        let num = shift_rounded(magnitude(&self.bits), minus, shift, false);
+        let (bits, overflow) = to_int(&num, minus);
+        (Fixed::from_bits(bits), overflow)
+    }
+
+    /// Converts number like `overflowing_convert`, wrapping integer part when it does not fit.
+    #[inline]
+    pub fn wrapping_convert<const M: usize, const G: u32>(self) -> Fixed<Int<D, M>, G> {
+        self.overflowing_convert().0
+    }
+
+    /// Converts number like `overflowing_convert`. Returns `None` when integer part does not fit.
+    #[inline]
+    pub fn checked_convert<const M: usize, const G: u32>(self) -> Option<Fixed<Int<D, M>, G>> {
+        match self.overflowing_convert() {
+            (_, true) => None,
+            (res, false) => Some(res),
+        }
+    }
+
+    /// Converts number like `overflowing_convert`, saturating to the bound when integer part does not fit.
+    pub fn saturating_convert<const M: usize, const G: u32>(self) -> Fixed<Int<D, M>, G> {
+        match self.overflowing_convert() {
+            (res, false) => res,
+            (_, true) => Fixed::saturated(self.is_negative()),
+        }
+    }
+
+    /// Returns the bound, which overflowing result with given sign saturates to.
+    fn saturated(negative: bool) -> Self {
+        if negative {
+            Self::min_value()
+        } else {
+            Self::max_value()
+        }
+    }
+
+    /// Negates number. Result wraps and overflow flag is set when it's `min_value()`.
+    pub fn overflowing_neg(self) -> (Self, bool) {
+        let (bits, overflow) = self.bits.overflowing_neg();
+        (Self::from_bits(bits), overflow)
+    }
+
+    /// Adds numbers. Result wraps and overflow flag is set when sum does not fit.
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let (bits, overflow) = self.bits.overflowing_add(rhs.bits);
+        (Self::from_bits(bits), overflow)
+    }
+
+    /// Subtracts numbers. Result wraps and overflow flag is set when difference does not fit.
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let (bits, overflow) = self.bits.overflowing_sub(rhs.bits);
+        (Self::from_bits(bits), overflow)
+    }
+
+    /// Multiplies numbers and shifts product right by `FRAC_BITS`, which rounds it towards negative infinity.
+    /// Result wraps and overflow flag is set when product does not fit.
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        let minus = self.is_negative() != rhs.is_negative();
+        let mut product = VecBigNum::new_zeroed();
+        SimpleEngine::mul_resize(&mut product, &magnitude(&self.bits), &magnitude(&rhs.bits));
+        product.normalize();
+        let product = shift_rounded(product, minus, -(FRAC_BITS as i64), false);
+        let (bits, overflow) = to_int(&product, minus);
+        (Self::from_bits(bits), overflow)
+    }
+
+    /// Shifts dividend left by `FRAC_BITS` and divides it, which rounds quotient towards zero.
+    /// Result wraps and overflow flag is set when quotient does not fit.
+    ///
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+        assert!(rhs.bits != Int::zero(), "attempt to divide by zero");
+
+        let minus = self.is_negative() != rhs.is_negative();
+        let mut num = magnitude(&self.bits);
+        SimpleEngine::shift_left_resize(&mut num, FRAC_BITS as u64);
+        let mut quot = VecBigNum::new_zeroed();
+        let mut rem = VecBigNum::new_zeroed();
+        SimpleEngine::div_rem_resize(&mut quot, &mut rem, &num, &magnitude(&rhs.bits));
+        quot.normalize();
+        let (bits, overflow) = to_int(&quot, minus);
+        (Self::from_bits(bits), overflow)
+    }
+
+    #[inline]
+    pub fn wrapping_neg(self) -> Self {
+        self.overflowing_neg().0
+    }
+
+    #[inline]
+    pub fn checked_neg(self) -> Option<Self> {
+        match self.overflowing_neg() {
+            (_, true) => None,
+            (res, false) => Some(res),
+        }
+    }
+
+    pub fn saturating_neg(self) -> Self {
+        match self.overflowing_neg() {
+            (res, false) => res,
+            (_, true) => Self::max_value(),
+        }
+    }
+
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        match self.overflowing_add(rhs) {
+            (res, false) => res,
+            (_, true) => Self::saturated(rhs.is_negative()),
+        }
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        match self.overflowing_sub(rhs) {
+            (res, false) => res,
+            (_, true) => Self::saturated(!rhs.is_negative()),
+        }
+    }
+
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        match self.overflowing_mul(rhs) {
+            (res, false) => res,
+            (_, true) => Self::saturated(self.is_negative() != rhs.is_negative()),
+        }
+    }
+
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    pub fn saturating_div(self, rhs: Self) -> Self {
+        match self.overflowing_div(rhs) {
+            (res, false) => res,
+            (_, true) => Self::saturated(self.is_negative() != rhs.is_negative()),
+        }
+    }
+
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    #[inline]
+    pub fn wrapping_div(self, rhs: Self) -> Self {
+        self.overflowing_div(rhs).0
+    }
+
+    /// Returns `None` when `rhs` is zero or division overflows.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.bits == Int::zero() {
+            return None;
+        }
+        match self.overflowing_div(rhs) {
+            (_, true) => None,
+            (res, false) => Some(res),
+        }
+    }
+}
+
+macro_rules! derive_fixed_wrapping_checked {
+    ($($overflowing:ident => $wrapping:ident, $checked:ident;)*) => {
+        impl<D, const N: usize, const FRAC_BITS: u32> Fixed<Int<D, N>, FRAC_BITS>
+        where
+            D: UnsignedNumDigit,
+        {
+            $(
+                #[inline]
+                pub fn $wrapping(self, rhs: Self) -> Self {
+                    self.$overflowing(rhs).0
+                }
+
+                #[inline]
+                pub fn $checked(self, rhs: Self) -> Option<Self> {
+                    match self.$overflowing(rhs) {
+                        (_, true) => None,
+                        (res, false) => Some(res),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+derive_fixed_wrapping_checked! {
+    overflowing_add => wrapping_add, checked_add;
+    overflowing_sub => wrapping_sub, checked_sub;
+    overflowing_mul => wrapping_mul, checked_mul;
+}
+
+impl<D, const N: usize, const FRAC_BITS: u32> fmt::Display for Fixed<Int<D, N>, FRAC_BITS>
+where
+    D: UnsignedNumDigit,
+{
+    /// Formats number in decimal. When precision is given, value is rounded to that many fractional digits
+    /// with ties to even. Otherwise the shortest representation, which parses back to the same number, is used.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exact = self.to_decimal();
+        let decimal = match f.precision() {
+            Some(digits) => exact.rescale(digits, RoundingMode::NearestEven),
+            None => (0..FRAC_BITS as usize)
+                .map(|digits| exact.rescale(digits, RoundingMode::NearestEven))
+                .find(|decimal| Self::from_decimal(decimal) == Some(*self))
+                .unwrap_or(exact),
+        };
+        fmt::Display::fmt(&decimal, f)
+    }
+}
+
+impl<D, const N: usize, const FRAC_BITS: u32> FromStr for Fixed<Int<D, N>, FRAC_BITS>
+where
+    D: UnsignedNumDigit,
+{
+    type Err = ParseDecimalError;
+
+    /// Parses decimal number like `-1.25` and rounds it to the nearest representable value with ties to even.
+    fn from_str(text: &str) -> Result<Self, ParseDecimalError> {
+        let decimal = text.parse::<BigDecimal<D>>()?;
+        Self::from_decimal(&decimal).ok_or(ParseDecimalError::OutOfRange)
+    }
+}
+
+macro_rules! derive_fixed_op {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $overflowing:ident, $check:ident, $msg:expr) => {
+        impl<D, const N: usize, const FRAC_BITS: u32> $trait for Fixed<Int<D, N>, FRAC_BITS>
+        where
+            D: UnsignedNumDigit,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: Self) -> Self {
+                let (res, overflow) = self.$overflowing(rhs);
+                $check!(!overflow, $msg);
+                res
+            }
+        }
+
+        impl<D, const N: usize, const FRAC_BITS: u32> $assign_trait for Fixed<Int<D, N>, FRAC_BITS>
+        where
+            D: UnsignedNumDigit,
+        {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $trait::$fn(*self, rhs);
+            }
+        }
+    };
+}
+
+derive_fixed_op!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    overflowing_add,
+    debug_assert,
+    "attempt to add with overflow"
+);
+derive_fixed_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    overflowing_sub,
+    debug_assert,
+    "attempt to subtract with overflow"
+);
+derive_fixed_op!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    overflowing_mul,
+    debug_assert,
+    "attempt to multiply with overflow"
+);
+derive_fixed_op!(
+    Div,
+    div,
+    DivAssign,
+    div_assign,
+    overflowing_div,
+    assert,
+    "attempt to divide with overflow"
+);
+
+impl<D, const N: usize, const FRAC_BITS: u32> Neg for Fixed<Int<D, N>, FRAC_BITS>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        let (res, overflow) = self.overflowing_neg();
+        debug_assert!(!overflow, "attempt to negate with overflow");
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::num::I256;
+
+    type Q15 = Fixed<Int<u16, 1>, 15>;
+    type Q16 = Fixed<Int<u8, 4>, 16>;
+
+    fn q16(v: i32) -> Q16 {
+        Q16::from_bits(Int::from(v))
+    }
+
+    const VALUES: &[i32] = &[
+        0,
+        1,
+        -1,
+        0x8000,
+        -0x8000,
+        0x10000,
+        -0x10000,
+        0x18000,
+        -0x28001,
+        123456789,
+        -987654321,
+        i32::MAX,
+        i32::MIN,
+        i32::MIN + 1,
+    ];
+
+    #[test]
+    fn test_q15() {
+        for a in (i16::MIN..=i16::MAX).step_by(37) {
+            for b in (i16::MIN..=i16::MAX).step_by(1013) {
+                let (l, r) = (Q15::from_bits(Int::from(a)), Q15::from_bits(Int::from(b)));
+                let product = (a as i32 * b as i32) >> 15;
+                assert_eq!(
+                    l.overflowing_mul(r),
+                    (
+                        Q15::from_bits(Int::from(product as i16)),
+                        product != product as i16 as i32
+                    )
+                );
+                if b != 0 {
+                    let quot = ((a as i32) << 15) / b as i32;
+                    assert_eq!(
+                        l.overflowing_div(r),
+                        (
+                            Q15::from_bits(Int::from(quot as i16)),
+                            quot != quot as i16 as i32
+                        )
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        for &a in VALUES {
+            for &b in VALUES {
+                let (l, r) = (q16(a), q16(b));
+                let product = (a as i64 * b as i64) >> 16;
+                let overflow = product != product as i32 as i64;
+                assert_eq!(l.overflowing_mul(r), (q16(product as i32), overflow));
+                let saturated = product.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+                assert_eq!(l.saturating_mul(r), q16(saturated));
+
+                let (sum, overflow) = a.overflowing_add(b);
+                assert_eq!(l.overflowing_add(r), (q16(sum), overflow));
+                assert_eq!(l.saturating_add(r), q16(a.saturating_add(b)));
+                assert_eq!(l.saturating_sub(r), q16(a.saturating_sub(b)));
+
+                if b == 0 {
+                    assert_eq!(l.checked_div(r), None);
+                    continue;
+                }
+                let quot = ((a as i64) << 16) / b as i64;
+                let overflow = quot != quot as i32 as i64;
+                assert_eq!(l.overflowing_div(r), (q16(quot as i32), overflow));
+                let saturated = quot.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+                assert_eq!(l.saturating_div(r), q16(saturated));
+            }
+            assert_eq!(q16(a).saturating_neg(), q16(a.saturating_neg()));
+            assert_eq!(q16(a).to_int_floor(), Int::from(a >> 16));
+        }
+
+        assert_eq!(Q16::one(), q16(0x10000));
+        assert_eq!(Q16::from_int(Int::from(-3)), Some(q16(-0x30000)));
+        assert_eq!(Q16::from_int(Int::from(-32768)), Some(Q16::min_value()));
+        assert_eq!(Q16::from_int(Int::from(32768)), None);
+        assert_eq!(q16(3 << 15) * q16(-1 << 16), q16(-3 << 15));
+        assert_eq!(q16(1 << 16) / q16(3 << 16), q16(21845));
+    }
+
+    #[test]
+    fn test_f64() {
+        assert_eq!(Q16::from_f64(1.5), Some(q16(0x18000)));
+        assert_eq!(Q16::from_f64(-0.25), Some(q16(-0x4000)));
+        // ties are rounded to even
+        assert_eq!(Q16::from_f64(0.5f64.powi(17)), Some(q16(0)));
+        assert_eq!(Q16::from_f64(3.0 * 0.5f64.powi(17)), Some(q16(2)));
+        assert_eq!(Q16::from_f64(-3.0 * 0.5f64.powi(17)), Some(q16(-2)));
+        assert_eq!(Q16::from_f64(-32768.0), Some(Q16::min_value()));
+        assert_eq!(Q16::from_f64(32768.0), None);
+        assert_eq!(Q16::from_f64(f64::NAN), None);
+
+        for &a in VALUES {
+            assert_eq!(q16(a).to_f64(), a as f64 / 65536.0);
+            assert_eq!(Q16::from_f64(q16(a).to_f64()), Some(q16(a)));
+        }
+
+        // 5.000...01 * 2^-1075 is rounded to subnormal 3 * 2^-1074 directly, while rounding to 53 bits first
+        // gives tie 5 * 2^-1075, which is rounded to even 2 * 2^-1074
+        let tiny = Fixed::<Int<u64, 1>, 1135>::from_bits(Int::from((5i64 << 60) + 1));
+        assert_eq!(tiny.to_f64(), 3.0 * f64::from_bits(1));
+    }
+
+    #[test]
+    fn test_strings() {
+        assert_eq!("1.25".parse::<Q16>(), Ok(q16(0x14000)));
+        assert_eq!("-0.1".parse::<Q16>(), Ok(q16(-6554)));
+        assert_eq!("32768".parse::<Q16>(), Err(ParseDecimalError::OutOfRange));
+        assert_eq!(
+            "1.2.3".parse::<Q16>(),
+            Err(ParseDecimalError::InvalidCharacter)
+        );
+
+        assert_eq!(q16(0x14000).to_string(), "1.25");
+        assert_eq!(q16(-6554).to_string(), "-0.1");
+        assert_eq!(q16(-0x30000).to_string(), "-3");
+        assert_eq!(q16(1).to_string(), "0.00002");
+        assert_eq!(format!("{:.3}", q16(205887)), "3.142");
+        assert_eq!(format!("{:.20}", q16(1)), "0.00001525878906250000");
+        assert_eq!(q16(1).to_decimal().to_string(), "0.0000152587890625");
+        for &a in VALUES {
+            assert_eq!(q16(a).to_string().parse::<Q16>(), Ok(q16(a)));
+        }
+    }
+
+    #[test]
+    fn test_accumulator() {
+        type Sample = Fixed<Int<u32, 1>, 15>;
+        type Accumulator = Fixed<I256, 128>;
+
+        let mut state = 12345u32;
+        let mut sample = || {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            let v = (state >> 16) as i16 as i32;
+            (Sample::from_bits(Int::from(v)), v as i64)
+        };
+
+        let mut acc = Accumulator::zero();
+        let mut expected = 0i64;
+        for _ in 0..10000 {
+            let (a, x) = sample();
+            let (b, y) = sample();
+            acc += a.checked_convert::<8, 128>().unwrap() * b.checked_convert().unwrap();
+            expected += x * y;
+        }
+        // products of Q15 numbers have 30 fractional bits, so the sum is exact
+        assert_eq!(
+            acc.checked_convert::<2, 30>(),
+            Some(Fixed::from_bits(Int::from(expected)))
+        );
+        assert_eq!(
+            acc.checked_convert::<1, 15>(),
+            Some(Sample::from_bits(Int::from((expected >> 15) as i32)))
+        );
+        let scaled = acc * Accumulator::from_int(Int::from(1i64 << 40)).unwrap();
+        assert_eq!(
+            scaled.saturating_convert::<1, 15>(),
+            Sample::saturated(expected < 0)
+        );
+        assert_eq!(acc.to_f64(), expected as f64 / (1u64 << 30) as f64);
+    }
+}
@@ -0,0 +1,747 @@
+use crate::iutil::{forward_ref_op, from_u64, shift_left, shift_right};
+use crate::num::{
+    BigNum, BigNumBits, DefaultBigNumDigit, DynamicBigNum, SignedVecBigNum, UnsignedNumDigit,
+    VecBigNum,
+};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+use core::ops::*;
+
+/// Mode of rounding results, which can't be represented exactly with requested precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+    /// Rounds to the nearest value. Ties are rounded to value with even last digit, which is also known as
+    /// banker's rounding.
+    #[default]
+    NearestEven,
+    /// Rounds to the nearest value. Ties are rounded away from zero.
+    NearestAway,
+    /// Rounds towards zero, which truncates the value.
+    TowardZero,
+    /// Rounds towards positive infinity.
+    Up,
+    /// Rounds towards negative infinity.
+    Down,
+}
+
+/// Returns true if any of `n` least significant bits of `num` is set.
+fn has_bits_below<D>(num: &VecBigNum<D>, n: usize) -> bool
+where
+    D: UnsignedNumDigit,
+{
+    !num.is_zero() && num.trailing_zeros() < n
+}
+
+/// Returns square root of `n` rounded down.
+pub(crate) fn isqrt<D>(n: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    if n.is_zero() {
+        return VecBigNum::new_zeroed();
+    }
+
+    // Newton iteration decreases monotonically when started above the root
+    let mut x = from_u64(1);
+    shift_left(&mut x, (n.bit_len() as u64 + 1) / 2);
+    loop {
+        let mut quot = VecBigNum::new_zeroed();
+        let mut rem = VecBigNum::new_zeroed();
+        SimpleEngine::div_rem_resize(&mut quot, &mut rem, n, &x);
+        SimpleEngine::add_resize(&mut quot, &x);
+        shift_right(&mut quot, 1);
+        if SimpleEngine::compare(&quot, &x) != Ordering::Less {
+            return x;
+        }
+        x = quot;
+    }
+}
+
+/// Binary floating point number of arbitrary precision.
+///
+/// Value is `mantissa * 2^exponent`, where mantissa has at most `precision` bits.
+/// Mantissa is kept odd, so each value has single representation. There are no infinities, NaNs and negative zero.
+///
+/// Operations take precision and rounding mode explicitly. Operators use bigger precision of operands
+/// and `RoundingMode::NearestEven`. Results are always correctly rounded.
+#[derive(Debug, Clone)]
+pub struct BigFloat<D = DefaultBigNumDigit> {
+    minus: bool,
+    mantissa: VecBigNum<D>,
+    exponent: i64,
+    precision: usize,
+}
+
+impl<D> BigFloat<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Returns zero with given precision.
+    ///
+    /// # Panic
+    /// Panics when precision is zero.
+    pub fn zero(precision: usize) -> Self {
+        assert!(precision > 0, "Precision must be at least one bit");
+        Self {
+            minus: false,
+            mantissa: VecBigNum::new_zeroed(),
+            exponent: 0,
+            precision,
+        }
+    }
+
+    /// Returns one with given precision.
+    ///
+    /// # Panic
+    /// Panics when precision is zero.
+    pub fn one(precision: usize) -> Self {
+        assert!(precision > 0, "Precision must be at least one bit");
+        Self {
+            minus: false,
+            mantissa: from_u64(1),
+            exponent: 0,
+            precision,
+        }
+    }
+
+    /// Creates number `(-1)^minus * mantissa * 2^exponent` rounded to `precision` bits.
+    /// `sticky` tells that exact value is a bit bigger in magnitude than the one given, which happens when
+    /// lower part of result was dropped.
+    fn round(
+        minus: bool,
+        mut mantissa: VecBigNum<D>,
+        mut exponent: i64,
+        sticky: bool,
+        precision: usize,
+        mode: RoundingMode,
+    ) -> Self {
+        assert!(precision > 0, "Precision must be at least one bit");
+        mantissa.normalize();
+        if mantissa.is_zero() && !sticky {
+            return Self::zero(precision);
+        }
+
+        // at least two bits below precision are needed to put sticky bit below rounding bit
+        let bits = mantissa.bit_len();
+        if sticky && bits < precision + 2 {
+            let extra = precision + 2 - bits;
+            shift_left(&mut mantissa, extra as u64);
+            exponent -= extra as i64;
+        }
+
+        let bits = mantissa.bit_len();
+        let mut round_bit = false;
+        let mut sticky = sticky;
+        if bits > precision {
+            let shift = bits - precision;
+            round_bit = mantissa.test_bit(shift - 1);
+            sticky = sticky || has_bits_below(&mantissa, shift - 1);
+            shift_right(&mut mantissa, shift as u64);
+            exponent += shift as i64;
+        }
+
+        let inexact = round_bit || sticky;
+        let increment = match mode {
+            RoundingMode::NearestEven => round_bit && (sticky || mantissa.test_bit(0)),
+            RoundingMode::NearestAway => round_bit,
+            RoundingMode::TowardZero => false,
+            RoundingMode::Up => inexact && !minus,
+            RoundingMode::Down => inexact && minus,
+        };
+        if increment {
+            SimpleEngine::add_resize(&mut mantissa, &from_u64(1));
+            if mantissa.bit_len() > precision {
+                // mantissa was all ones and became power of two
+                shift_right(&mut mantissa, 1);
+                exponent += 1;
+            }
+        }
+
+        if mantissa.is_zero() {
+            return Self::zero(precision);
+        }
+        let zeros = mantissa.trailing_zeros();
+        shift_right(&mut mantissa, zeros as u64);
+        Self {
+            minus,
+            mantissa,
+            exponent: exponent + zeros as i64,
+            precision,
+        }
+    }
+
+    /// Creates number from integer rounded to `precision` bits.
+    pub fn from_int(num: &SignedVecBigNum<D>, precision: usize, mode: RoundingMode) -> Self {
+        Self::round(
+            num.is_negative(),
+            num.magnitude().clone(),
+            0,
+            false,
+            precision,
+            mode,
+        )
+    }
+
+    /// Creates number `mantissa * 2^exponent` rounded to `precision` bits.
+    pub fn from_parts(
+        mantissa: &SignedVecBigNum<D>,
+        exponent: i64,
+        precision: usize,
+        mode: RoundingMode,
+    ) -> Self {
+        Self::round(
+            mantissa.is_negative(),
+            mantissa.magnitude().clone(),
+            exponent,
+            false,
+            precision,
+            mode,
+        )
+    }
+
+    /// Creates number with exactly the same value as `v` and 53 bits of precision.
+    /// Returns `None` for infinities and NaN.
+    pub fn from_f64(v: f64) -> Option<Self> {
+        if !v.is_finite() {
+            return None;
+        }
+
+        let bits = v.to_bits();
+        let biased_exp = ((bits >> 52) & 0x7ff) as i64;
+        let fraction = bits & ((1 << 52) - 1);
+        let (mantissa, exponent) = if biased_exp == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), biased_exp - 1075)
+        };
+        Some(Self::round(
+            bits >> 63 == 1,
+            from_u64(mantissa),
+            exponent,
+            false,
+            53,
+            RoundingMode::NearestEven,
+        ))
+    }
+
+    /// Returns the nearest `f64`, ties are rounded to even.
+    /// Values too big for `f64` become infinity and too small ones become zero.
+    pub fn to_f64(&self) -> f64 {
+        if self.is_zero() {
+            return 0.0;
+        }
+
+        // subnormal numbers have less bits, since their lowest bit is always 2^-1074
+        // number is rounded only once, so the multiplication below must be exact
+        let top = self.exponent + self.mantissa.bit_len() as i64 - 1;
+        let precision = (top + 1075).clamp(0, 53) as usize;
+        if precision == 0 {
+            // number is smaller than 2^-1074, so it's zero or the smallest subnormal, if it's above half of it
+            let res = if top == -1075 && !self.mantissa.is_one() {
+                f64::from_bits(1)
+            } else {
+                0.0
+            };
+            return if self.minus { -res } else { res };
+        }
+
+        let rounded = self.with_precision(precision, RoundingMode::NearestEven);
+        let mut mantissa = 0u64;
+        for bit in 0..rounded.mantissa.bit_len() {
+            if rounded.mantissa.test_bit(bit) {
+                mantissa |= 1 << bit;
+            }
+        }
+
+        // exponent is applied in two steps, so intermediate result does not overflow nor underflow
+        let exponent = rounded.exponent.clamp(-4000, 4000) as i32;
+        let half = exponent / 2;
+        let res = mantissa as f64 * 2f64.powi(half) * 2f64.powi(exponent - half);
+        if rounded.minus {
+            -res
+        } else {
+            res
+        }
+    }
+
+    /// Returns this number rounded to `precision` bits.
+    pub fn with_precision(&self, precision: usize, mode: RoundingMode) -> Self {
+        Self::round(
+            self.minus,
+            self.mantissa.clone(),
+            self.exponent,
+            false,
+            precision,
+            mode,
+        )
+    }
+
+    /// Returns precision of this number in bits.
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Returns mantissa, which is odd for nonzero numbers.
+    pub fn mantissa(&self) -> SignedVecBigNum<D> {
+        SignedVecBigNum::from_parts(self.mantissa.clone(), self.minus)
+    }
+
+    /// Returns exponent of mantissa.
+    pub fn exponent(&self) -> i64 {
+        self.exponent
+    }
+
+    /// Returns true if value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.mantissa.is_zero()
+    }
+
+    /// Returns true if value is smaller than zero.
+    pub fn is_negative(&self) -> bool {
+        self.minus
+    }
+
+    /// Returns absolute value.
+    pub fn abs(&self) -> Self {
+        let mut res = self.clone();
+        res.minus = false;
+        res
+    }
+
+    /// Returns position of the bit just above the most significant set bit.
+    /// Value of nonzero number is in range `[2^(top - 1), 2^top)`.
+    fn top(&self) -> i64 {
+        self.exponent + self.mantissa.bit_len() as i64
+    }
+
+    /// Compares absolute values.
+    fn cmp_abs(&self, other: &Self) -> Ordering {
+        match (self.is_zero(), other.is_zero()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            (false, false) => {}
+        }
+        match self.top().cmp(&other.top()) {
+            Ordering::Equal => {}
+            ord => return ord,
+        }
+
+        // the same top, so the difference of exponents is smaller than the bigger mantissa
+        let (mut lhs, mut rhs) = (self.mantissa.clone(), other.mantissa.clone());
+        if self.exponent > other.exponent {
+            shift_left(&mut lhs, (self.exponent - other.exponent) as u64);
+        } else {
+            shift_left(&mut rhs, (other.exponent - self.exponent) as u64);
+        }
+        SimpleEngine::compare(&lhs, &rhs)
+    }
+
+    /// Adds `rhs` with sign negated when `negate_rhs` is set.
+    fn add_signed(
+        &self,
+        rhs: &Self,
+        negate_rhs: bool,
+        precision: usize,
+        mode: RoundingMode,
+    ) -> Self {
+        let rhs_minus = rhs.minus != negate_rhs;
+        if rhs.is_zero() {
+            return self.with_precision(precision, mode);
+        }
+        if self.is_zero() {
+            return Self::round(
+                rhs_minus,
+                rhs.mantissa.clone(),
+                rhs.exponent,
+                false,
+                precision,
+                mode,
+            );
+        }
+
+        // a is the bigger one in magnitude
+        let (a, a_minus, b, b_minus) = if self.cmp_abs(rhs) != Ordering::Less {
+            (self, self.minus, rhs, rhs_minus)
+        } else {
+            (rhs, rhs_minus, self, self.minus)
+        };
+
+        // when b is far below a, it can be replaced with any smaller value below the lowest bit of a
+        // and below rounding position of result, since it affects only the sticky bit
+        let mut b_mantissa = b.mantissa.clone();
+        let mut b_exponent = b.exponent;
+        let cutoff = a.exponent.min(a.top() - precision as i64 - 3) - 1;
+        if b.top() <= cutoff {
+            b_mantissa = from_u64(1);
+            b_exponent = cutoff - 1;
+        }
+
+        let exponent = a.exponent.min(b_exponent);
+        let mut lhs = a.mantissa.clone();
+        shift_left(&mut lhs, (a.exponent - exponent) as u64);
+        shift_left(&mut b_mantissa, (b_exponent - exponent) as u64);
+
+        if a_minus == b_minus {
+            SimpleEngine::add_resize(&mut lhs, &b_mantissa);
+        } else {
+            // |a| >= |b|, so it can't borrow
+            let borrow = SimpleEngine::sub_resize(&mut lhs, &b_mantissa);
+            debug_assert!(!borrow);
+        }
+        Self::round(a_minus, lhs, exponent, false, precision, mode)
+    }
+
+    /// Returns `self + rhs` rounded to `precision` bits.
+    pub fn add_with(&self, rhs: &Self, precision: usize, mode: RoundingMode) -> Self {
+        self.add_signed(rhs, false, precision, mode)
+    }
+
+    /// Returns `self - rhs` rounded to `precision` bits.
+    pub fn sub_with(&self, rhs: &Self, precision: usize, mode: RoundingMode) -> Self {
+        self.add_signed(rhs, true, precision, mode)
+    }
+
+    /// Returns `self * rhs` rounded to `precision` bits.
+    pub fn mul_with(&self, rhs: &Self, precision: usize, mode: RoundingMode) -> Self {
+        let mut mantissa = VecBigNum::new_zeroed();
+        SimpleEngine::mul_resize(&mut mantissa, &self.mantissa, &rhs.mantissa);
+        Self::round(
+            self.minus != rhs.minus,
+            mantissa,
+            self.exponent + rhs.exponent,
+            false,
+            precision,
+            mode,
+        )
+    }
+
+    /// Returns `self / rhs` rounded to `precision` bits.
+    ///
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    pub fn div_with(&self, rhs: &Self, precision: usize, mode: RoundingMode) -> Self {
+        assert!(!rhs.is_zero(), "Division by zero");
+        if self.is_zero() {
+            return Self::zero(precision);
+        }
+
+        // quotient needs at least two bits more than precision
+        let shift =
+            (precision + 2 + rhs.mantissa.bit_len()).saturating_sub(self.mantissa.bit_len());
+        let mut lhs = self.mantissa.clone();
+        shift_left(&mut lhs, shift as u64);
+
+        let mut quot = VecBigNum::new_zeroed();
+        let mut rem = VecBigNum::new_zeroed();
+        SimpleEngine::div_rem_resize(&mut quot, &mut rem, &lhs, &rhs.mantissa);
+        Self::round(
+            self.minus != rhs.minus,
+            quot,
+            self.exponent - rhs.exponent - shift as i64,
+            !rem.is_zero(),
+            precision,
+            mode,
+        )
+    }
+
+    /// Returns square root rounded to `precision` bits.
+    ///
+    /// # Panic
+    /// Panics when number is negative.
+    pub fn sqrt_with(&self, precision: usize, mode: RoundingMode) -> Self {
+        assert!(!self.minus, "Square root of negative number");
+        if self.is_zero() {
+            return Self::zero(precision);
+        }
+
+        // root needs at least two bits more than precision and exponent has to be even
+        let mut shift = (2 * (precision + 2)).saturating_sub(self.mantissa.bit_len()) as i64;
+        if (self.exponent - shift) % 2 != 0 {
+            shift += 1;
+        }
+        let mut value = self.mantissa.clone();
+        shift_left(&mut value, shift as u64);
+
+        let root = isqrt(&value);
+        let mut square = VecBigNum::new_zeroed();
+        SimpleEngine::mul_resize(&mut square, &root, &root);
+        let exact = SimpleEngine::compare(&square, &value) == Ordering::Equal;
+        Self::round(
+            false,
+            root,
+            (self.exponent - shift) / 2,
+            !exact,
+            precision,
+            mode,
+        )
+    }
+
+    /// Returns square root rounded to precision of this number, ties to even.
+    ///
+    /// # Panic
+    /// Panics when number is negative.
+    pub fn sqrt(&self) -> Self {
+        self.sqrt_with(self.precision, RoundingMode::NearestEven)
+    }
+}
+
+impl<D> PartialEq for BigFloat<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Compares values, precision is ignored.
+    fn eq(&self, other: &Self) -> bool {
+        // representation is unique
+        self.minus == other.minus
+            && self.exponent == other.exponent
+            && self.mantissa == other.mantissa
+    }
+}
+
+impl<D> Eq for BigFloat<D> where D: UnsignedNumDigit {}
+
+impl<D> PartialOrd for BigFloat<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D> Ord for BigFloat<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.minus, other.minus) {
+            (false, false) => self.cmp_abs(other),
+            (true, true) => other.cmp_abs(self),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+        }
+    }
+}
+
+impl<D> Neg for &BigFloat<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = BigFloat<D>;
+
+    fn neg(self) -> BigFloat<D> {
+        let mut res = self.clone();
+        res.minus = !res.minus && !res.is_zero();
+        res
+    }
+}
+
+impl<D> Neg for BigFloat<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        -&self
+    }
+}
+
+macro_rules! derive_float_op {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $with:ident) => {
+        impl<D> $trait for &BigFloat<D>
+        where
+            D: UnsignedNumDigit,
+        {
+            type Output = BigFloat<D>;
+
+            #[inline]
+            fn $fn(self, rhs: Self) -> BigFloat<D> {
+                let precision = self.precision.max(rhs.precision);
+                self.$with(rhs, precision, RoundingMode::NearestEven)
+            }
+        }
+
+        forward_ref_op!([D] BigFloat<D>, $trait, $fn, $assign_trait, $assign_fn);
+    };
+}
+
+derive_float_op!(Add, add, AddAssign, add_assign, add_with);
+derive_float_op!(Sub, sub, SubAssign, sub_assign, sub_with);
+derive_float_op!(Mul, mul, MulAssign, mul_assign, mul_with);
+derive_float_op!(Div, div, DivAssign, div_assign, div_with);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::iutil::xorshift;
+
+    /// Returns random finite double with exponent in reasonable range.
+    fn random_f64(state: &mut u64) -> f64 {
+        let bits = xorshift(state);
+        let exp = 1023 - 60 + (bits >> 52) % 120;
+        f64::from_bits((bits & (1 << 63 | ((1 << 52) - 1))) | exp << 52)
+    }
+
+    fn float(v: f64) -> BigFloat<u32> {
+        BigFloat::from_f64(v).unwrap()
+    }
+
+    #[test]
+    fn test_f64_conversion() {
+        for &v in &[
+            0.0,
+            1.0,
+            -1.5,
+            0.1,
+            1e300,
+            -1e-300,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f64::from_bits(1),
+        ] {
+            assert_eq!(float(v).to_f64(), v);
+        }
+        assert_eq!(float(-0.0), BigFloat::zero(53));
+        assert_eq!(float(6.0).mantissa(), SignedVecBigNum::from(from_u64(3)));
+        assert_eq!(float(6.0).exponent(), 1);
+        assert!(BigFloat::<u32>::from_f64(f64::NAN).is_none());
+
+        // subnormal results are rounded only once
+        let parts = |mantissa: u64, exponent: i64| {
+            let mantissa = SignedVecBigNum::from(from_u64(mantissa));
+            BigFloat::<u32>::from_parts(&mantissa, exponent, 128, RoundingMode::NearestEven)
+        };
+        let min = f64::from_bits(1);
+        for &(mantissa, exponent, expected) in &[
+            ((1 << 60) + 1, -1135, min),
+            (1, -1075, 0.0),
+            (3, -1076, min),
+            (1, -1076, 0.0),
+            (u64::MAX, -1140, 0.0),
+            (5, -1075, 2.0 * min),
+            (7, -1075, 4.0 * min),
+            ((5 << 40) + 1, -1115, 3.0 * min),
+            ((1 << 53) - 1, -1075, f64::MIN_POSITIVE),
+            ((1 << 53) - 3, -1075, f64::from_bits((1 << 52) - 2)),
+        ] {
+            let v = parts(mantissa, exponent);
+            assert_eq!(v.to_f64(), expected, "{} * 2^{}", mantissa, exponent);
+            assert_eq!((-v).to_f64(), -expected, "-{} * 2^{}", mantissa, exponent);
+        }
+        let subnormal = f64::from_bits(0x000f_ffff_ffff_ffff);
+        assert_eq!(float(subnormal).to_f64(), subnormal);
+    }
+
+    #[test]
+    fn test_matches_f64() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        let mode = RoundingMode::NearestEven;
+        for _ in 0..2000 {
+            let (a, b) = (random_f64(&mut state), random_f64(&mut state));
+            let (x, y) = (float(a), float(b));
+            assert_eq!(x.add_with(&y, 53, mode).to_f64(), a + b, "{} + {}", a, b);
+            assert_eq!(x.sub_with(&y, 53, mode).to_f64(), a - b, "{} - {}", a, b);
+            assert_eq!(x.mul_with(&y, 53, mode).to_f64(), a * b, "{} * {}", a, b);
+            assert_eq!(x.div_with(&y, 53, mode).to_f64(), a / b, "{} / {}", a, b);
+            assert_eq!(x.abs().sqrt().to_f64(), a.abs().sqrt(), "sqrt {}", a);
+            assert_eq!(x.cmp(&y), a.partial_cmp(&b).unwrap());
+        }
+
+        // operands far apart
+        let (a, b) = (1.0, 1e-30);
+        assert_eq!((float(a) + float(b)).to_f64(), a + b);
+        assert_eq!((float(a) - float(b)).to_f64(), a - b);
+        assert_eq!(
+            float(a)
+                .sub_with(&float(b), 53, RoundingMode::Down)
+                .to_f64(),
+            1.0 - f64::EPSILON / 2.0
+        );
+        assert_eq!(
+            float(a).add_with(&float(b), 53, RoundingMode::Up).to_f64(),
+            1.0 + f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let third = |mode| float(1.0).div_with(&float(3.0), 4, mode);
+        let neg_third = |mode| float(-1.0).div_with(&float(3.0), 4, mode);
+        let sixteenths = |v: f64| float(v / 32.0);
+
+        assert_eq!(third(RoundingMode::NearestEven), sixteenths(11.0));
+        assert_eq!(third(RoundingMode::TowardZero), sixteenths(10.0));
+        assert_eq!(third(RoundingMode::Up), sixteenths(11.0));
+        assert_eq!(third(RoundingMode::Down), sixteenths(10.0));
+        assert_eq!(neg_third(RoundingMode::NearestEven), sixteenths(-11.0));
+        assert_eq!(neg_third(RoundingMode::TowardZero), sixteenths(-10.0));
+        assert_eq!(neg_third(RoundingMode::Up), sixteenths(-10.0));
+        assert_eq!(neg_third(RoundingMode::Down), sixteenths(-11.0));
+
+        // ties go to even mantissa
+        let tie = |v: f64| {
+            float(v)
+                .with_precision(2, RoundingMode::NearestEven)
+                .to_f64()
+        };
+        assert_eq!(tie(5.0), 4.0);
+        assert_eq!(tie(7.0), 8.0);
+        assert_eq!(tie(-5.0), -4.0);
+        let tie_away = |v: f64| {
+            float(v)
+                .with_precision(2, RoundingMode::NearestAway)
+                .to_f64()
+        };
+        assert_eq!(tie_away(5.0), 6.0);
+        assert_eq!(tie_away(-5.0), -6.0);
+        assert_eq!(tie_away(7.0), 8.0);
+        assert_eq!(
+            float(7.0)
+                .with_precision(2, RoundingMode::TowardZero)
+                .to_f64(),
+            6.0
+        );
+    }
+
+    #[test]
+    fn test_high_precision() {
+        // sqrt(2) with 200 bits, squared back with 400 bits is close to 2
+        let two = BigFloat::<u64>::from_int(
+            &SignedVecBigNum::from(from_u64(2)),
+            200,
+            RoundingMode::NearestEven,
+        );
+        let root = two.sqrt();
+        assert_eq!(root.precision(), 200);
+        assert_eq!(root.mantissa().magnitude().bit_len(), 200);
+        let error = root.mul_with(&root, 400, RoundingMode::NearestEven) - two.clone();
+        assert!(
+            error.abs()
+                < BigFloat::from_parts(
+                    &SignedVecBigNum::from(from_u64(1)),
+                    -197,
+                    53,
+                    RoundingMode::NearestEven
+                )
+        );
+
+        // 1/3 * 3 rounds back to one
+        let three = BigFloat::<u64>::from_f64(3.0)
+            .unwrap()
+            .with_precision(300, RoundingMode::NearestEven);
+        let third =
+            BigFloat::from_f64(1.0)
+                .unwrap()
+                .div_with(&three, 300, RoundingMode::NearestEven);
+        assert_eq!(&third * &three, BigFloat::from_f64(1.0).unwrap());
+        assert_eq!(third.to_f64(), 1.0 / 3.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_division_by_zero() {
+        let _ = float(1.0) / BigFloat::zero(10);
+    }
+}
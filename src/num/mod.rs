@@ -1,8 +1,43 @@
 mod digit;
 pub use self::digit::*;
 
+mod double;
+pub use self::double::*;
+
+#[allow(clippy::module_inception)]
 mod num;
 pub use self::num::*;
 
 mod ext;
 pub use self::ext::*;
+
+mod bits;
+pub use self::bits::*;
+
+mod prime;
+pub use self::prime::*;
+
+mod modular;
+pub use self::modular::*;
+
+mod field;
+pub use self::field::*;
+
+mod rational;
+pub use self::rational::*;
+
+mod float;
+pub use self::float::*;
+
+mod elementary;
+
+mod decimal;
+pub use self::decimal::*;
+
+mod fixed;
+pub use self::fixed::*;
+
+#[cfg(feature = "rand")]
+pub(crate) mod random;
+#[cfg(feature = "rand")]
+pub use self::random::*;
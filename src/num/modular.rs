@@ -0,0 +1,520 @@
+use crate::iutil::forward_ref_op;
+use crate::num::{BigNum, BigNumBits, DynamicBigNum, UnsignedNumDigit, VecBigNum};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::*;
+
+/// Returns `b^exp`, where `b` is digit base.
+fn base_power<D>(exp: usize) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = VecBigNum::new_zeroed_sized(exp + 1);
+    res[exp] = D::ONE;
+    res
+}
+
+/// Parameters used to reduce products modulo specific modulus.
+#[derive(Debug, Clone)]
+enum Reduction<D> {
+    /// Used for odd moduli. Residues are kept in Montgomery form, which is `x * R mod m` for `R = b^len`.
+    Montgomery {
+        /// `-m^-1 mod b`.
+        m_inv: D,
+        /// `R^2 mod m`, which converts numbers into Montgomery form.
+        r2: VecBigNum<D>,
+    },
+    /// Used for even moduli, which can't use Montgomery form.
+    Barrett {
+        /// `floor(b^(2 * len) / m)`.
+        mu: VecBigNum<D>,
+    },
+}
+
+/// Modulus of `ModInt`, with cached parameters of modular reduction.
+///
+/// Odd moduli use Montgomery multiplication and even ones use Barrett reduction.
+/// Computing parameters requires division, so single modulus should be created once and shared by all its residues.
+#[derive(Debug, Clone)]
+pub struct Modulus<D> {
+    value: VecBigNum<D>,
+    len: usize,
+    reduction: Reduction<D>,
+}
+
+impl<D> Modulus<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Creates modulus with given value.
+    ///
+    /// # Panic
+    /// Panics when `value` is zero.
+    pub fn new(value: &impl BigNum<Digit = D>) -> Self {
+        assert!(!value.is_zero(), "Modulus must not be zero");
+
+        let len = value.significant_len();
+        let mut m = VecBigNum::new_zeroed_sized(len);
+        for i in 0..len {
+            m[i] = value[i];
+        }
+
+        let mut quot = VecBigNum::new_zeroed();
+        let mut rem = VecBigNum::new_zeroed();
+        SimpleEngine::div_rem_resize(&mut quot, &mut rem, &base_power(2 * len), &m);
+
+        let reduction = if m[0] & D::ONE == D::ONE {
+            // Newton iteration doubles amount of correct low bits and m * m = 1 mod 8
+            let mut inv = m[0];
+            let mut correct_bits = 3;
+            while correct_bits < D::NUM_BITS {
+                let two = D::ONE + D::ONE;
+                inv = inv.wrapping_mul(two.wrapping_sub(m[0].wrapping_mul(inv)));
+                correct_bits *= 2;
+            }
+            Reduction::Montgomery {
+                m_inv: D::ZERO.wrapping_sub(inv),
+                r2: rem,
+            }
+        } else {
+            Reduction::Barrett { mu: quot }
+        };
+
+        Self {
+            value: m,
+            len,
+            reduction,
+        }
+    }
+
+    /// Returns value of modulus.
+    pub fn value(&self) -> &VecBigNum<D> {
+        &self.value
+    }
+
+    /// Returns true when Montgomery multiplication is used, which happens for odd moduli.
+    pub fn is_montgomery(&self) -> bool {
+        matches!(self.reduction, Reduction::Montgomery { .. })
+    }
+
+    /// Reduces any number modulo this modulus.
+    fn reduce_any(&self, value: &impl BigNum<Digit = D>) -> VecBigNum<D> {
+        let mut quot = VecBigNum::new_zeroed();
+        let mut rem = VecBigNum::new_zeroed();
+        SimpleEngine::div_rem_resize(&mut quot, &mut rem, value, &self.value);
+        rem
+    }
+
+    /// Subtracts modulus from `value` if it's not smaller than modulus.
+    fn final_subtraction(&self, mut value: VecBigNum<D>) -> VecBigNum<D> {
+        if SimpleEngine::compare(&value, &self.value) != Ordering::Less {
+            SimpleEngine::sub_resize(&mut value, &self.value);
+        }
+        value
+    }
+
+    /// Computes `t * R^-1 mod m` for `t < m * R`(Montgomery reduction).
+    fn montgomery_reduce(&self, mut t: VecBigNum<D>, m_inv: D) -> VecBigNum<D> {
+        let len = self.len;
+        t.resize(2 * len + 1);
+        for i in 0..len {
+            // adding u * m * b^i clears digit at position i
+            let u = t[i].wrapping_mul(m_inv);
+            let mut carry = D::ZERO;
+            for j in 0..len {
+                let (lo, hi) = u.digit_carrying_mul(self.value[j], carry, t[i + j]);
+                t[i + j] = lo;
+                carry = hi;
+            }
+            let mut pos = i + len;
+            while carry != D::ZERO {
+                let (sum, overflow) = t[pos].overflowing_add(carry);
+                t[pos] = sum;
+                carry = if overflow { D::ONE } else { D::ZERO };
+                pos += 1;
+            }
+        }
+
+        // result is smaller than 2m
+        let mut res = VecBigNum::new_zeroed_sized(len + 1);
+        for i in 0..=len {
+            res[i] = t[len + i];
+        }
+        res.normalize();
+        self.final_subtraction(res)
+    }
+
+    /// Computes `x mod m` for `x < m^2` using precomputed `mu`(Barrett reduction).
+    fn barrett_reduce(&self, x: VecBigNum<D>, mu: &VecBigNum<D>) -> VecBigNum<D> {
+        let len = self.len;
+        let digit_bits = D::NUM_BITS as u64;
+
+        // q = floor(floor(x / b^(len - 1)) * mu / b^(len + 1)) is at most 2 smaller than floor(x / m)
+        let mut q1 = x.clone();
+        SimpleEngine::shift_right_u64(&mut q1, (len as u64 - 1) * digit_bits);
+        let mut q = VecBigNum::new_zeroed();
+        SimpleEngine::mul_resize(&mut q, &q1, mu);
+        SimpleEngine::shift_right_u64(&mut q, (len as u64 + 1) * digit_bits);
+
+        let mut qm = VecBigNum::new_zeroed();
+        SimpleEngine::mul_resize(&mut qm, &q, &self.value);
+        let mut res = x;
+        let borrow = SimpleEngine::sub_resize(&mut res, &qm);
+        debug_assert!(!borrow);
+
+        res = self.final_subtraction(res);
+        res = self.final_subtraction(res);
+        res.normalize();
+        res
+    }
+
+    /// Multiplies two residues in internal form.
+    fn mul(&self, lhs: &VecBigNum<D>, rhs: &VecBigNum<D>) -> VecBigNum<D> {
+        let mut prod = VecBigNum::new_zeroed();
+        SimpleEngine::mul_resize(&mut prod, lhs, rhs);
+        match &self.reduction {
+            Reduction::Montgomery { m_inv, .. } => self.montgomery_reduce(prod, *m_inv),
+            Reduction::Barrett { mu } => self.barrett_reduce(prod, mu),
+        }
+    }
+
+    /// Converts residue into internal form.
+    fn to_internal(&self, value: VecBigNum<D>) -> VecBigNum<D> {
+        match &self.reduction {
+            Reduction::Montgomery { r2, .. } => self.mul(&value, r2),
+            Reduction::Barrett { .. } => value,
+        }
+    }
+
+    /// Converts number in internal form back to residue.
+    fn to_residue(&self, value: &VecBigNum<D>) -> VecBigNum<D> {
+        match &self.reduction {
+            Reduction::Montgomery { m_inv, .. } => self.montgomery_reduce(value.clone(), *m_inv),
+            Reduction::Barrett { .. } => value.clone(),
+        }
+    }
+}
+
+impl<D> PartialEq for Modulus<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<D> Eq for Modulus<D> where D: UnsignedNumDigit {}
+
+/// Integer modulo `m`, which borrows its shared `Modulus`.
+///
+/// All operations return results reduced modulo `m`.
+///
+/// # Panic
+/// Binary operators panic when operands have different moduli.
+#[derive(Debug, Clone)]
+pub struct ModInt<'a, D> {
+    /// Residue in internal form of modulus.
+    value: VecBigNum<D>,
+    modulus: &'a Modulus<D>,
+}
+
+impl<'a, D> ModInt<'a, D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Creates residue of `value` modulo `modulus`.
+    /// Value may be bigger than modulus.
+    pub fn new(value: &impl BigNum<Digit = D>, modulus: &'a Modulus<D>) -> Self {
+        let value = modulus.to_internal(modulus.reduce_any(value));
+        Self { value, modulus }
+    }
+
+    /// Returns zero modulo `modulus`.
+    pub fn zero(modulus: &'a Modulus<D>) -> Self {
+        Self {
+            value: VecBigNum::new_zeroed(),
+            modulus,
+        }
+    }
+
+    /// Returns one modulo `modulus`.
+    pub fn one(modulus: &'a Modulus<D>) -> Self {
+        Self::new(&VecBigNum::from(vec![D::ONE]), modulus)
+    }
+
+    /// Returns modulus of this number.
+    pub fn modulus(&self) -> &'a Modulus<D> {
+        self.modulus
+    }
+
+    /// Returns residue, which is in range `[0, m)`.
+    pub fn value(&self) -> VecBigNum<D> {
+        self.modulus.to_residue(&self.value)
+    }
+
+    /// Returns true if residue is zero.
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    fn check_modulus(&self, other: &Self) {
+        assert!(
+            core::ptr::eq(self.modulus, other.modulus) || self.modulus == other.modulus,
+            "Numbers have different moduli"
+        );
+    }
+
+    /// Raises this number to power `exp`.
+    /// Zero raised to zero is one.
+    pub fn pow(&self, exp: &impl BigNum<Digit = D>) -> Self {
+        let mut res = Self::one(self.modulus);
+        for bit in (0..exp.bit_len()).rev() {
+            res.value = self.modulus.mul(&res.value, &res.value);
+            if exp.test_bit(bit) {
+                res.value = self.modulus.mul(&res.value, &self.value);
+            }
+        }
+        res
+    }
+
+    /// Returns multiplicative inverse of this number.
+    /// Returns `None` when it does not exist, which happens when residue is not coprime with modulus.
+    pub fn inv(&self) -> Option<Self> {
+        let mut res = VecBigNum::new_zeroed();
+        if !SimpleEngine::inv_mod_resize(&mut res, &self.value(), &self.modulus.value) {
+            return None;
+        }
+        Some(Self {
+            value: self.modulus.to_internal(res),
+            modulus: self.modulus,
+        })
+    }
+}
+
+impl<'a, D> PartialEq for ModInt<'a, D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Numbers are equal when they have the same residue modulo the same modulus.
+    fn eq(&self, other: &Self) -> bool {
+        self.modulus == other.modulus && self.value == other.value
+    }
+}
+
+impl<'a, D> Eq for ModInt<'a, D> where D: UnsignedNumDigit {}
+
+impl<'a, D> fmt::Display for ModInt<'a, D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Formats residue in decimal.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value(), f)
+    }
+}
+
+impl<'a, 'b, D> Add<&'b ModInt<'a, D>> for &'b ModInt<'a, D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = ModInt<'a, D>;
+
+    fn add(self, rhs: Self) -> ModInt<'a, D> {
+        self.check_modulus(rhs);
+        let mut value = self.value.clone();
+        SimpleEngine::add_resize(&mut value, &rhs.value);
+        ModInt {
+            value: self.modulus.final_subtraction(value),
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl<'a, 'b, D> Sub<&'b ModInt<'a, D>> for &'b ModInt<'a, D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = ModInt<'a, D>;
+
+    fn sub(self, rhs: Self) -> ModInt<'a, D> {
+        self.check_modulus(rhs);
+        let mut value = self.value.clone();
+        if SimpleEngine::compare(&value, &rhs.value) == Ordering::Less {
+            SimpleEngine::add_resize(&mut value, &self.modulus.value);
+        }
+        SimpleEngine::sub_resize(&mut value, &rhs.value);
+        value.normalize();
+        ModInt {
+            value,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl<'a, 'b, D> Mul<&'b ModInt<'a, D>> for &'b ModInt<'a, D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = ModInt<'a, D>;
+
+    fn mul(self, rhs: Self) -> ModInt<'a, D> {
+        self.check_modulus(rhs);
+        ModInt {
+            value: self.modulus.mul(&self.value, &rhs.value),
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl<'a, 'b, D> Div<&'b ModInt<'a, D>> for &'b ModInt<'a, D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = ModInt<'a, D>;
+
+    /// # Panic
+    /// Panics when `rhs` is not invertible.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> ModInt<'a, D> {
+        self.check_modulus(rhs);
+        let inv = rhs.inv().expect("Divisor is not invertible");
+        self * &inv
+    }
+}
+
+impl<'a, D> Neg for &ModInt<'a, D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = ModInt<'a, D>;
+
+    fn neg(self) -> ModInt<'a, D> {
+        &ModInt::zero(self.modulus) - self
+    }
+}
+
+impl<'a, D> Neg for ModInt<'a, D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        -&self
+    }
+}
+
+forward_ref_op!(['a, D] ModInt<'a, D>, Add, add, AddAssign, add_assign);
+forward_ref_op!(['a, D] ModInt<'a, D>, Sub, sub, SubAssign, sub_assign);
+forward_ref_op!(['a, D] ModInt<'a, D>, Mul, mul, MulAssign, mul_assign);
+forward_ref_op!(['a, D] ModInt<'a, D>, Div, div, DivAssign, div_assign);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::iutil::from_u64;
+
+    const MODULI: &[u64] = &[
+        1,
+        2,
+        3,
+        97,
+        256,
+        1_000_000_007,
+        0xffff_fffe,
+        0xffff_ffff_ffff_ffc5,
+        0x8000_0000_0000_0000,
+    ];
+
+    fn pow_mod_u64(base: u64, mut exp: u64, m: u64) -> u64 {
+        let mut res = 1 % m as u128;
+        let mut base = base as u128 % m as u128;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = res * base % m as u128;
+            }
+            base = base * base % m as u128;
+            exp >>= 1;
+        }
+        res as u64
+    }
+
+    fn check_modulus<D>(m: u64)
+    where
+        D: UnsignedNumDigit,
+    {
+        let modulus = Modulus::new(&from_u64::<D>(m));
+        assert_eq!(modulus.is_montgomery(), m % 2 == 1);
+
+        let values = [0, 1, 2, m / 2, m - 1, m, 0xdead_beef_cafe_babe];
+        for &a in values.iter() {
+            for &b in values.iter() {
+                let x = ModInt::new(&from_u64::<D>(a), &modulus);
+                let y = ModInt::new(&from_u64::<D>(b), &modulus);
+                let (a, b, m) = (a as u128 % m as u128, b as u128 % m as u128, m as u128);
+
+                let sum = (a + b) % m;
+                let diff = (a + m - b) % m;
+                let prod = a * b % m;
+                assert_eq!((&x + &y).value(), from_u64(sum as u64));
+                assert_eq!((&x - &y).value(), from_u64(diff as u64));
+                assert_eq!((&x * &y).value(), from_u64(prod as u64));
+                assert_eq!((-&y).value(), from_u64(((m - b) % m) as u64));
+                assert_eq!(
+                    x.pow(&from_u64(b as u64)).value(),
+                    from_u64(pow_mod_u64(a as u64, b as u64, m as u64))
+                );
+
+                if let Some(inv) = y.inv() {
+                    assert!((&inv * &y).value().is_one() || m == 1);
+                    assert_eq!(&(&x / &y) * &y, x);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_int_arithmetic() {
+        for &m in MODULI {
+            check_modulus::<u8>(m);
+            check_modulus::<u32>(m);
+            check_modulus::<u64>(m);
+            check_modulus::<u128>(m);
+        }
+    }
+
+    #[test]
+    fn test_mod_int_operators() {
+        let modulus = Modulus::new(&from_u64::<u32>(1_000_000_007));
+        let other = Modulus::new(&from_u64::<u32>(1_000_000_007));
+        let a = ModInt::new(&from_u64(123_456_789_012), &modulus);
+        let b = ModInt::new(&from_u64(987_654_321), &other);
+
+        let mut c = a.clone();
+        c += &b;
+        c *= b.clone();
+        c -= &a;
+        c /= b.clone();
+        assert_eq!(
+            c,
+            (a.clone() + b.clone()) * b.clone() / b.clone() - a.clone() / b.clone()
+        );
+        assert_eq!(a.to_string(), "456788151");
+        assert_eq!(format!("{:>10}", ModInt::one(&modulus)), "         1");
+        assert_ne!(
+            a,
+            ModInt::new(&from_u64(456_788_151), &Modulus::new(&from_u64(7)))
+        );
+        assert!(ModInt::zero(&modulus).inv().is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mod_int_different_moduli() {
+        let a = Modulus::new(&from_u64::<u32>(7));
+        let b = Modulus::new(&from_u64::<u32>(11));
+        let _ = ModInt::one(&a) + ModInt::one(&b);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::num::{hash_value, BigNum, BigNumMut, UnsignedBigNum, UnsignedNumDigit};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+use core::ops::{Index, IndexMut};
+
+/// Bignum with constant amount of digits, which does not use heap.
+///
+/// Equality, ordering and hashing are value based: high zero digits are ignored,
+/// so it can be compared with any other `UnsignedBigNum` with the same digit type.
+#[derive(Debug, Clone, Copy)]
+pub struct ArrayBigNum<D, const N: usize> {
+    num: [D; N],
+}
+
+impl<D, const N: usize> ArrayBigNum<D, N> {
+    pub fn into_inner(self) -> [D; N] {
+        self.num
+    }
+}
+
+impl<D, const N: usize> From<[D; N]> for ArrayBigNum<D, N> {
+    /// Creates number from little endian digits.
+    fn from(num: [D; N]) -> Self {
+        Self { num }
+    }
+}
+
+impl<D, const N: usize> From<ArrayBigNum<D, N>> for [D; N] {
+    fn from(num: ArrayBigNum<D, N>) -> Self {
+        num.num
+    }
+}
+
+impl<D, const N: usize> Index<usize> for ArrayBigNum<D, N> {
+    type Output = D;
+
+    #[inline]
+    fn index(&self, index: usize) -> &D {
+        &self.num[index]
+    }
+}
+
+impl<D, const N: usize> IndexMut<usize> for ArrayBigNum<D, N> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut D {
+        &mut self.num[index]
+    }
+}
+
+impl<D, const N: usize> BigNumMut for ArrayBigNum<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn set_digit(&mut self, pos: usize, digit: Self::Digit) {
+        self.num[pos] = digit;
+    }
+}
+
+impl<D, const N: usize> BigNum for ArrayBigNum<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    type Digit = D;
+
+    #[inline]
+    fn new_zeroed() -> Self {
+        Self { num: [D::ZERO; N] }
+    }
+
+    #[inline]
+    fn get_digit(&self, pos: usize) -> Self::Digit {
+        self.num[pos]
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        N
+    }
+}
+
+impl<D, const N: usize> UnsignedBigNum for ArrayBigNum<D, N> where D: UnsignedNumDigit {}
+
+impl<D, const N: usize> Default for ArrayBigNum<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new_zeroed()
+    }
+}
+
+impl<D, T, const N: usize> PartialEq<T> for ArrayBigNum<D, N>
+where
+    D: UnsignedNumDigit,
+    T: UnsignedBigNum<Digit = D>,
+{
+    #[inline]
+    fn eq(&self, other: &T) -> bool {
+        SimpleEngine::compare(self, other) == Ordering::Equal
+    }
+}
+
+impl<D, const N: usize> Eq for ArrayBigNum<D, N> where D: UnsignedNumDigit {}
+
+impl<D, T, const N: usize> PartialOrd<T> for ArrayBigNum<D, N>
+where
+    D: UnsignedNumDigit,
+    T: UnsignedBigNum<Digit = D>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        Some(SimpleEngine::compare(self, other))
+    }
+}
+
+impl<D, const N: usize> Ord for ArrayBigNum<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        SimpleEngine::compare(self, other)
+    }
+}
+
+impl<D, const N: usize> Hash for ArrayBigNum<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(self, state)
+    }
+}
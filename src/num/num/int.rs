@@ -0,0 +1,789 @@
+use crate::num::{ArrayBigNum, BigNum, BigNumMut, DefaultBigNumDigit, UnsignedNumDigit};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::ops::*;
+
+/// Signed integer with constant amount of digits in two's complement representation.
+///
+/// Unlike `SignedBigNum` types digits of `Int` are not absolute value of number but its two's complement bits,
+/// so unsigned engines perform wrapping addition, subtraction and multiplication on it directly.
+///
+/// Operators behave just like for primitive integers: they panic on overflow when debug assertions are enabled
+/// and wrap otherwise. Division truncates towards zero and remainder has sign of dividend.
+///
+/// It's not `UnsignedBigNum`, so it can't be compared with unsigned bignums, whose digits are the same bits.
+/// Use `unsigned_abs` to compare absolute value.
+///
+/// ```compile_fail
+/// use varnum::num::{ArrayBigNum, Int};
+///
+/// assert!(ArrayBigNum::from([0xffu8; 8]) > Int::<u8, 8>::from(-1i64));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Int<D, const N: usize> {
+    num: [D; N],
+}
+
+/// Signed 256 bit integer.
+pub type I256 = Int<DefaultBigNumDigit, { 256 / (core::mem::size_of::<DefaultBigNumDigit>() * 8) }>;
+
+/// Signed 512 bit integer.
+pub type I512 = Int<DefaultBigNumDigit, { 512 / (core::mem::size_of::<DefaultBigNumDigit>() * 8) }>;
+
+impl<D, const N: usize> Int<D, N> {
+    /// Returns little endian two's complement digits of this number.
+    pub fn into_inner(self) -> [D; N] {
+        self.num
+    }
+}
+
+impl<D, const N: usize> From<[D; N]> for Int<D, N> {
+    /// Creates number from little endian two's complement digits.
+    fn from(num: [D; N]) -> Self {
+        Self { num }
+    }
+}
+
+impl<D, const N: usize> Int<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    /// Size of this integer in bits.
+    pub const BITS: u32 = N as u32 * D::NUM_BITS;
+
+    #[inline]
+    pub fn zero() -> Self {
+        Self { num: [D::ZERO; N] }
+    }
+
+    #[inline]
+    pub fn one() -> Self {
+        Self::from_i128(1)
+    }
+
+    /// Returns smallest value, which can be represented by this integer.
+    /// Integer without digits can only represent zero.
+    pub fn min_value() -> Self {
+        let mut num = [D::ZERO; N];
+        if let Some(d) = num.last_mut() {
+            *d = D::ONE << (D::NUM_BITS - 1);
+        }
+        Self { num }
+    }
+
+    /// Returns biggest value, which can be represented by this integer.
+    /// Integer without digits can only represent zero.
+    pub fn max_value() -> Self {
+        let mut num = [D::MAX; N];
+        if let Some(d) = num.last_mut() {
+            *d = D::MAX >> 1;
+        }
+        Self { num }
+    }
+
+    /// Creates integer from primitive one.
+    /// Value is truncated if it does not fit.
+    pub fn from_i128(v: i128) -> Self {
+        let mut num = [if v < 0 { D::MAX } else { D::ZERO }; N];
+
+        let mask = if D::NUM_BITS >= 128 {
+            u128::MAX
+        } else {
+            (1u128 << D::NUM_BITS) - 1
+        };
+        let mut bits = v as u128;
+        for d in num.iter_mut().take((128 / D::NUM_BITS) as usize) {
+            *d = <D as TryFrom<u128>>::try_from(bits & mask).unwrap_or_else(|_| unreachable!());
+            bits = bits.checked_shr(D::NUM_BITS).unwrap_or(0);
+        }
+
+        Self { num }
+    }
+
+    /// Returns true if this number is smaller than zero.
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        N > 0 && (self.num[N - 1] >> (D::NUM_BITS - 1)) != D::ZERO
+    }
+
+    /// Returns true if this number is greater than zero.
+    #[inline]
+    pub fn is_positive(&self) -> bool {
+        !self.is_negative() && self.num.iter().any(|d| *d != D::ZERO)
+    }
+
+    /// Returns -1, 0 or 1 depending on sign of this number.
+    pub fn signum(&self) -> Self {
+        if self.is_negative() {
+            Self::from_i128(-1)
+        } else if self.is_positive() {
+            Self::one()
+        } else {
+            Self::zero()
+        }
+    }
+
+    /// Converts integer to one with `M` digits.
+    ///
+    /// When `M > N` number is sign extended, so its value is preserved.
+    /// Otherwise it's truncated to `M` least significant digits just like `as` cast does.
+    pub fn resize<const M: usize>(&self) -> Int<D, M> {
+        let mut num = [if self.is_negative() { D::MAX } else { D::ZERO }; M];
+        for (dst, src) in num.iter_mut().zip(self.num.iter()) {
+            *dst = *src;
+        }
+        Int { num }
+    }
+
+    /// Returns absolute value of this number as an unsigned number.
+    /// Never overflows, since absolute value of `min_value()` fits unsigned number with the same size.
+    pub fn unsigned_abs(&self) -> ArrayBigNum<D, N> {
+        let abs = if self.is_negative() {
+            self.wrapping_neg()
+        } else {
+            *self
+        };
+        ArrayBigNum::from(abs.num)
+    }
+
+    pub fn wrapping_abs(self) -> Self {
+        Self::from(self.unsigned_abs().into_inner())
+    }
+
+    pub fn overflowing_neg(self) -> (Self, bool) {
+        let mut res = self;
+        let mut carry = true;
+        for d in res.num.iter_mut() {
+            let (n, overflow) = (!*d).overflowing_add(if carry { D::ONE } else { D::ZERO });
+            carry = overflow;
+            *d = n;
+        }
+        (res, self.is_negative() && res.is_negative())
+    }
+
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let mut res = self;
+        SimpleEngine::add_accumulate(&mut res, &rhs);
+
+        let overflow =
+            self.is_negative() == rhs.is_negative() && res.is_negative() != self.is_negative();
+        (res, overflow)
+    }
+
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let mut res = self;
+        SimpleEngine::sub_accumulate(&mut res, &rhs);
+
+        let overflow =
+            self.is_negative() != rhs.is_negative() && res.is_negative() != self.is_negative();
+        (res, overflow)
+    }
+
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        // multiply absolute values; low bits of negated result are the same as low bits of real product
+        let mut product = ArrayBigNum::new_zeroed();
+        let mut overflow =
+            SimpleEngine::mul_accumulate(&mut product, &self.unsigned_abs(), &rhs.unsigned_abs());
+
+        let negative = self.is_negative() != rhs.is_negative();
+        let product = Self::from(product.into_inner());
+
+        // absolute value of result must be smaller than 2^(BITS - 1) or equal to it if result is negative
+        if product.is_negative() {
+            overflow |= !(negative && product == Self::min_value());
+        }
+
+        if negative {
+            (product.wrapping_neg(), overflow)
+        } else {
+            (product, overflow)
+        }
+    }
+
+    /// Computes quotient and remainder at once.
+    ///
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    pub fn overflowing_div_rem(self, rhs: Self) -> (Self, Self, bool) {
+        assert!(rhs != Self::zero(), "attempt to divide by zero");
+
+        let mut quot = ArrayBigNum::new_zeroed();
+        let mut rem = ArrayBigNum::new_zeroed();
+        SimpleEngine::div_rem(
+            &mut quot,
+            &mut rem,
+            &self.unsigned_abs(),
+            &rhs.unsigned_abs(),
+        );
+
+        let mut quot = Self::from(quot.into_inner());
+        let mut rem = Self::from(rem.into_inner());
+        if self.is_negative() != rhs.is_negative() {
+            quot = quot.wrapping_neg();
+        }
+        if self.is_negative() {
+            rem = rem.wrapping_neg();
+        }
+
+        // the only case: min_value() / -1
+        let overflow = self == Self::min_value() && rhs == Self::from_i128(-1);
+        (quot, rem, overflow)
+    }
+
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+        let (quot, _, overflow) = self.overflowing_div_rem(rhs);
+        (quot, overflow)
+    }
+
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    pub fn overflowing_rem(self, rhs: Self) -> (Self, bool) {
+        let (_, rem, overflow) = self.overflowing_div_rem(rhs);
+        (rem, overflow)
+    }
+
+    /// Shifts number left by `n % BITS` bits.
+    /// Overflow flag is set when `n >= BITS`, just like for primitive integers.
+    pub fn overflowing_shl(self, n: u32) -> (Self, bool) {
+        let mut res = self;
+        if N > 0 {
+            SimpleEngine::shift_left_u32(&mut res, n % Self::BITS);
+        }
+        (res, n >= Self::BITS)
+    }
+
+    /// Arithmetic right shift by `n % BITS` bits, which fills high bits with sign bit.
+    /// Overflow flag is set when `n >= BITS`, just like for primitive integers.
+    pub fn overflowing_shr(self, n: u32) -> (Self, bool) {
+        let mut res = self;
+        if N == 0 {
+            return (res, true);
+        }
+
+        let shift = n % Self::BITS;
+        SimpleEngine::shift_right_u32(&mut res, shift);
+
+        if self.is_negative() && shift > 0 {
+            let digits = (shift / D::NUM_BITS) as usize;
+            let bits = shift % D::NUM_BITS;
+            for d in res.num[N - digits..].iter_mut() {
+                *d = D::MAX;
+            }
+            if bits > 0 {
+                res.num[N - 1 - digits] |= !(D::MAX >> bits);
+            }
+        }
+        (res, n >= Self::BITS)
+    }
+}
+
+macro_rules! derive_int_wrapping_checked {
+    ($($overflowing:ident => $wrapping:ident, $checked:ident;)*) => {
+        impl<D, const N: usize> Int<D, N>
+        where
+            D: UnsignedNumDigit,
+        {
+            $(
+                #[inline]
+                pub fn $wrapping(self, rhs: Self) -> Self {
+                    self.$overflowing(rhs).0
+                }
+
+                #[inline]
+                pub fn $checked(self, rhs: Self) -> Option<Self> {
+                    match self.$overflowing(rhs) {
+                        (_, true) => None,
+                        (res, false) => Some(res),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+derive_int_wrapping_checked! {
+    overflowing_add => wrapping_add, checked_add;
+    overflowing_sub => wrapping_sub, checked_sub;
+    overflowing_mul => wrapping_mul, checked_mul;
+}
+
+impl<D, const N: usize> Int<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    pub fn wrapping_neg(self) -> Self {
+        self.overflowing_neg().0
+    }
+
+    #[inline]
+    pub fn checked_neg(self) -> Option<Self> {
+        match self.overflowing_neg() {
+            (_, true) => None,
+            (res, false) => Some(res),
+        }
+    }
+
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    #[inline]
+    pub fn wrapping_div(self, rhs: Self) -> Self {
+        self.overflowing_div(rhs).0
+    }
+
+    /// Returns `None` when `rhs` is zero or division overflows.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs == Self::zero() {
+            return None;
+        }
+        match self.overflowing_div(rhs) {
+            (_, true) => None,
+            (res, false) => Some(res),
+        }
+    }
+
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    #[inline]
+    pub fn wrapping_rem(self, rhs: Self) -> Self {
+        self.overflowing_rem(rhs).0
+    }
+
+    /// Returns `None` when `rhs` is zero or division overflows.
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        if rhs == Self::zero() {
+            return None;
+        }
+        match self.overflowing_rem(rhs) {
+            (_, true) => None,
+            (res, false) => Some(res),
+        }
+    }
+
+    #[inline]
+    pub fn wrapping_shl(self, n: u32) -> Self {
+        self.overflowing_shl(n).0
+    }
+
+    #[inline]
+    pub fn checked_shl(self, n: u32) -> Option<Self> {
+        match self.overflowing_shl(n) {
+            (_, true) => None,
+            (res, false) => Some(res),
+        }
+    }
+
+    #[inline]
+    pub fn wrapping_shr(self, n: u32) -> Self {
+        self.overflowing_shr(n).0
+    }
+
+    #[inline]
+    pub fn checked_shr(self, n: u32) -> Option<Self> {
+        match self.overflowing_shr(n) {
+            (_, true) => None,
+            (res, false) => Some(res),
+        }
+    }
+}
+
+impl<D, const N: usize> Index<usize> for Int<D, N> {
+    type Output = D;
+
+    #[inline]
+    fn index(&self, index: usize) -> &D {
+        &self.num[index]
+    }
+}
+
+impl<D, const N: usize> IndexMut<usize> for Int<D, N> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut D {
+        &mut self.num[index]
+    }
+}
+
+impl<D, const N: usize> BigNum for Int<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    type Digit = D;
+
+    #[inline]
+    fn new_zeroed() -> Self {
+        Self::zero()
+    }
+
+    #[inline]
+    fn get_digit(&self, pos: usize) -> Self::Digit {
+        self.num[pos]
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        N
+    }
+}
+
+impl<D, const N: usize> BigNumMut for Int<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn set_digit(&mut self, pos: usize, digit: Self::Digit) {
+        self.num[pos] = digit;
+    }
+}
+
+impl<D, const N: usize> Default for Int<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<D, const N: usize> PartialOrd for Int<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D, const N: usize> Ord for Int<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            // for numbers with the same sign two's complement bits are ordered just like values
+            _ => SimpleEngine::compare(self, other),
+        }
+    }
+}
+
+macro_rules! derive_int_from_primitive {
+    ($($type:ty),*) => {
+        $(
+            impl<D, const N: usize> From<$type> for Int<D, N>
+            where
+                D: UnsignedNumDigit,
+            {
+                #[inline]
+                fn from(v: $type) -> Self {
+                    Self::from_i128(v as i128)
+                }
+            }
+        )*
+    };
+}
+
+derive_int_from_primitive!(i8, i16, i32, i64, i128);
+
+macro_rules! derive_int_op {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $rhs:ty, $overflowing:ident, $check:ident, $msg:expr) => {
+        impl<D, const N: usize> $trait<$rhs> for Int<D, N>
+        where
+            D: UnsignedNumDigit,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(self, rhs: $rhs) -> Self {
+                let (res, overflow) = self.$overflowing(rhs);
+                $check!(!overflow, $msg);
+                res
+            }
+        }
+
+        impl<D, const N: usize> $assign_trait<$rhs> for Int<D, N>
+        where
+            D: UnsignedNumDigit,
+        {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: $rhs) {
+                *self = $trait::$fn(*self, rhs);
+            }
+        }
+    };
+}
+
+derive_int_op!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    Self,
+    overflowing_add,
+    debug_assert,
+    "attempt to add with overflow"
+);
+derive_int_op!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    Self,
+    overflowing_sub,
+    debug_assert,
+    "attempt to subtract with overflow"
+);
+derive_int_op!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    Self,
+    overflowing_mul,
+    debug_assert,
+    "attempt to multiply with overflow"
+);
+derive_int_op!(
+    Div,
+    div,
+    DivAssign,
+    div_assign,
+    Self,
+    overflowing_div,
+    assert,
+    "attempt to divide with overflow"
+);
+derive_int_op!(
+    Rem,
+    rem,
+    RemAssign,
+    rem_assign,
+    Self,
+    overflowing_rem,
+    assert,
+    "attempt to calculate the remainder with overflow"
+);
+derive_int_op!(
+    Shl,
+    shl,
+    ShlAssign,
+    shl_assign,
+    u32,
+    overflowing_shl,
+    debug_assert,
+    "attempt to shift left with overflow"
+);
+derive_int_op!(
+    Shr,
+    shr,
+    ShrAssign,
+    shr_assign,
+    u32,
+    overflowing_shr,
+    debug_assert,
+    "attempt to shift right with overflow"
+);
+
+macro_rules! derive_int_bit_op {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $op:tt) => {
+        impl<D, const N: usize> $trait for Int<D, N>
+        where
+            D: UnsignedNumDigit,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(mut self, rhs: Self) -> Self {
+                $assign_trait::$assign_fn(&mut self, rhs);
+                self
+            }
+        }
+
+        impl<D, const N: usize> $assign_trait for Int<D, N>
+        where
+            D: UnsignedNumDigit,
+        {
+            #[inline]
+            fn $assign_fn(&mut self, rhs: Self) {
+                for (l, r) in self.num.iter_mut().zip(rhs.num.iter()) {
+                    *l $op *r;
+                }
+            }
+        }
+    };
+}
+
+derive_int_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &=);
+derive_int_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |=);
+derive_int_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^=);
+
+impl<D, const N: usize> Neg for Int<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        let (res, overflow) = self.overflowing_neg();
+        debug_assert!(!overflow, "attempt to negate with overflow");
+        res
+    }
+}
+
+impl<D, const N: usize> Not for Int<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = Self;
+
+    #[inline]
+    fn not(mut self) -> Self {
+        for d in self.num.iter_mut() {
+            *d = !*d;
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type I64 = Int<u8, 8>;
+    type I16 = Int<u8, 2>;
+
+    const VALUES: &[i64] = &[
+        0,
+        1,
+        -1,
+        2,
+        -2,
+        127,
+        -128,
+        255,
+        -256,
+        65535,
+        -65537,
+        123456789,
+        -987654321,
+        i32::MAX as i64,
+        i32::MIN as i64,
+        i64::MAX,
+        i64::MIN,
+        i64::MAX - 1,
+        i64::MIN + 1,
+    ];
+
+    #[test]
+    fn test_add_sub_mul() {
+        for &a in VALUES {
+            for &b in VALUES {
+                let (l, r) = (I64::from(a), I64::from(b));
+
+                let (res, overflow) = a.overflowing_add(b);
+                assert_eq!(l.overflowing_add(r), (I64::from(res), overflow));
+                let (res, overflow) = a.overflowing_sub(b);
+                assert_eq!(l.overflowing_sub(r), (I64::from(res), overflow));
+                let (res, overflow) = a.overflowing_mul(b);
+                assert_eq!(
+                    l.overflowing_mul(r),
+                    (I64::from(res), overflow),
+                    "{} * {}",
+                    a,
+                    b
+                );
+                assert_eq!(l.cmp(&r), a.cmp(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_div_rem() {
+        for &a in VALUES {
+            for &b in VALUES {
+                if b == 0 {
+                    assert_eq!(I64::from(a).checked_div(I64::from(b)), None);
+                    continue;
+                }
+                let (l, r) = (I64::from(a), I64::from(b));
+
+                let (res, overflow) = a.overflowing_div(b);
+                assert_eq!(l.overflowing_div(r), (I64::from(res), overflow));
+                let (res, overflow) = a.overflowing_rem(b);
+                assert_eq!(l.overflowing_rem(r), (I64::from(res), overflow));
+            }
+        }
+    }
+
+    #[test]
+    fn test_shifts() {
+        for &a in VALUES {
+            for n in 0..70 {
+                let (res, overflow) = a.overflowing_shl(n);
+                assert_eq!(I64::from(a).overflowing_shl(n), (I64::from(res), overflow));
+                let (res, overflow) = a.overflowing_shr(n);
+                assert_eq!(I64::from(a).overflowing_shr(n), (I64::from(res), overflow));
+            }
+        }
+    }
+
+    #[test]
+    fn test_i16() {
+        for a in (i16::MIN..=i16::MAX).step_by(37) {
+            for b in (i16::MIN..=i16::MAX).step_by(1013) {
+                let (l, r) = (I16::from(a), I16::from(b));
+                assert_eq!(l.wrapping_mul(r), I16::from(a.wrapping_mul(b)));
+                assert_eq!(l.wrapping_add(r), I16::from(a.wrapping_add(b)));
+                assert_eq!(l.wrapping_div(r), I16::from(a.wrapping_div(b)));
+                assert_eq!(l.wrapping_rem(r), I16::from(a.wrapping_rem(b)));
+                assert_eq!(!l & r, I16::from(!a & b));
+                assert_eq!(l ^ r, I16::from(a ^ b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_negative_is_not_unsigned() {
+        // absolute value of minimum has the same bits as minimum itself
+        for &a in VALUES.iter().filter(|&&a| a < 0 && a != i64::MIN) {
+            let bits = ArrayBigNum::from((a as u64).to_le_bytes());
+            let abs = I64::from(a).unsigned_abs();
+            assert_ne!(bits, abs, "{}", a);
+            assert_eq!(abs, ArrayBigNum::from(a.unsigned_abs().to_le_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_resize() {
+        for &a in VALUES {
+            let num = I64::from(a);
+            assert_eq!(num.resize::<16>(), Int::<u8, 16>::from(a));
+            assert_eq!(num.resize::<4>(), Int::<u8, 4>::from(a as i32));
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(I64::min_value(), I64::from(i64::MIN));
+        assert_eq!(I64::max_value(), I64::from(i64::MAX));
+        assert_eq!(I256::BITS, 256);
+        assert_eq!(I512::BITS, 512);
+        assert!(I256::min_value() < I256::from(i128::MIN));
+        assert_eq!(I256::min_value().checked_neg(), None);
+        assert_eq!(
+            I256::min_value().resize::<16>().checked_neg(),
+            Some(I256::max_value().resize::<16>() + Int::one())
+        );
+
+        type I0 = Int<u8, 0>;
+        assert_eq!(I0::min_value(), I0::zero());
+        assert_eq!(I0::max_value(), I0::zero());
+        assert!(!I0::min_value().is_negative());
+    }
+}
@@ -1,12 +1,31 @@
 use crate::num::UnsignedNumDigit;
+use core::hash::{Hash, Hasher};
 use core::ops::{Index, IndexMut};
 
 /// Default type of bignum.
 pub type DefaultBigNumDigit = u32;
 
+/// Widest digit, which target can multiply natively into a double-width result.
+/// On 64 bit targets it's `u64`, since `u128` digits use software multiplication.
+#[cfg(target_pointer_width = "64")]
+pub type NativeDigit = u64;
+
+/// Widest digit, which target can multiply natively into a double-width result.
+#[cfg(not(target_pointer_width = "64"))]
+pub type NativeDigit = usize;
+
 mod vec;
 pub use self::vec::*;
 
+mod signed_vec;
+pub use self::signed_vec::*;
+
+mod array;
+pub use self::array::*;
+
+mod int;
+pub use self::int::*;
+
 /// Single number, which is split into digits.
 pub trait BigNum: Sized + Clone + Index<usize, Output = Self::Digit> {
     /// Type of digit of this bignum.
@@ -22,6 +41,70 @@ pub trait BigNum: Sized + Clone + Index<usize, Output = Self::Digit> {
 
     /// Returns amount of digits of this bignum.
     fn len(&self) -> usize;
+
+    /// Returns true if this bignum has no digits at all.
+    /// Note: it's not the same as having value zero.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns amount of digits without zero digits above the most significant nonzero one.
+    /// It's zero for zero.
+    fn significant_len(&self) -> usize {
+        let mut sz = self.len();
+        while sz > 0 && self[sz - 1] == Self::Digit::ZERO {
+            sz -= 1;
+        }
+        sz
+    }
+
+    /// Returns true if value of this bignum is zero.
+    /// Bignum without any digits is zero as well.
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.significant_len() == 0
+    }
+
+    /// Returns true if value of this bignum is one.
+    #[inline]
+    fn is_one(&self) -> bool {
+        self.significant_len() == 1 && self[0] == Self::Digit::ONE
+    }
+}
+
+/// BigNum, whose digits are digits of nonnegative value of the number.
+///
+/// Value-based `PartialEq` and `PartialOrd` between different bignum types and primality testing
+/// are only implemented for these, since digits of signed numbers are not their value.
+///
+/// ```compile_fail
+/// use varnum::num::{Int, VecBigNum};
+///
+/// // digits are the same, but values are 2^64 - 1 and -1
+/// assert_ne!(VecBigNum::<u8>::from(vec![0xff; 8]), Int::<u8, 8>::from(-1i64));
+/// ```
+///
+/// ```compile_fail
+/// use varnum::num::{SignedVecBigNum, VecBigNum};
+///
+/// assert_ne!(VecBigNum::<u32>::from(vec![1]), SignedVecBigNum::from_parts(VecBigNum::from(vec![1]), true));
+/// ```
+pub trait UnsignedBigNum: BigNum {}
+
+/// Hashes value of bignum, so that numbers with the same value but different amount of high zero digits
+/// have the same hash.
+/// All bignum types should use it in their `Hash` implementations, so hashes are consistent with value-based `PartialEq`.
+pub(crate) fn hash_value<N, H>(num: &N, state: &mut H)
+where
+    N: BigNum,
+    H: Hasher,
+{
+    let sz = num.significant_len();
+    state.write_usize(sz);
+    for i in 0..sz {
+        num[i].hash(state);
+    }
 }
 
 /// BigNum, which is not immutable and can be mutated.
@@ -47,6 +130,52 @@ pub trait DynamicBigNum: BigNum {
 
     /// Creates new bignum with value zero and specified size.
     fn new_zeroed_sized(size: usize) -> Self;
+
+    /// Reserves capacity for at least `additional` more digits, so that resizing does not reallocate.
+    /// It's only a hint, by default it does nothing.
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+
+    /// Releases capacity, which is not used by digits.
+    /// It's only a hint, by default it does nothing.
+    #[inline]
+    fn shrink_to_fit(&mut self) {}
+
+    /// Appends digit, which becomes the new most significant digit.
+    #[inline]
+    fn push_digit(&mut self, digit: Self::Digit)
+    where
+        Self: BigNumMut,
+    {
+        let len = self.len();
+        self.resize(len + 1);
+        self.set_digit(len, digit);
+    }
+
+    /// Truncates number to `len` least significant digits.
+    /// Noop if number has no more than `len` digits.
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            self.resize(len);
+        }
+    }
+
+    /// Drops zero digits above the most significant nonzero one.
+    /// Value of number is not changed. Zero becomes number without any digits.
+    #[inline]
+    fn normalize(&mut self) {
+        let sz = self.significant_len();
+        self.truncate(sz);
+    }
+
+    /// Alias for `normalize`.
+    #[inline]
+    fn trim(&mut self) {
+        self.normalize()
+    }
 }
 
 /// BigNum, which has sign.
@@ -0,0 +1,286 @@
+use crate::num::{
+    hash_value, BigNum, BigNumMut, DefaultBigNumDigit, DynamicBigNum, SignedBigNum,
+    SignedBigNumMut, UnsignedNumDigit, VecBigNum,
+};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Index, IndexMut};
+
+/// Heap allocated signed bignum in sign-magnitude representation.
+///
+/// Digits are digits of absolute value, sign is stored separately.
+/// Equality, ordering and hashing are value based, so negative zero is equal to zero.
+/// It's not `UnsignedBigNum`, so it can't be compared with unsigned bignums directly.
+///
+/// ```compile_fail
+/// use varnum::num::{SignedVecBigNum, VecBigNum};
+///
+/// let minus_one = SignedVecBigNum::from_parts(VecBigNum::<u32>::from(vec![1]), true);
+/// assert!(VecBigNum::from(vec![1]) != minus_one);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SignedVecBigNum<D = DefaultBigNumDigit> {
+    num: VecBigNum<D>,
+    minus: bool,
+}
+
+impl<D> SignedVecBigNum<D> {
+    /// Creates number from absolute value and sign.
+    pub fn from_parts(num: VecBigNum<D>, minus: bool) -> Self {
+        Self { num, minus }
+    }
+
+    /// Returns absolute value and sign of this number.
+    pub fn into_parts(self) -> (VecBigNum<D>, bool) {
+        (self.num, self.minus)
+    }
+
+    /// Returns absolute value of this number.
+    pub fn magnitude(&self) -> &VecBigNum<D> {
+        &self.num
+    }
+}
+
+impl<D> SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Returns true if this number is smaller than zero.
+    /// Unlike `is_minus` it's false for negative zero.
+    pub fn is_negative(&self) -> bool {
+        self.minus && !self.num.is_zero()
+    }
+}
+
+impl<D> From<VecBigNum<D>> for SignedVecBigNum<D> {
+    /// Creates nonnegative number from its absolute value.
+    fn from(num: VecBigNum<D>) -> Self {
+        Self { num, minus: false }
+    }
+}
+
+impl<D> Index<usize> for SignedVecBigNum<D> {
+    type Output = D;
+
+    #[inline]
+    fn index(&self, index: usize) -> &D {
+        &self.num[index]
+    }
+}
+
+impl<D> IndexMut<usize> for SignedVecBigNum<D> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut D {
+        &mut self.num[index]
+    }
+}
+
+impl<D> BigNum for SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Digit = D;
+
+    #[inline]
+    fn new_zeroed() -> Self {
+        Self {
+            num: VecBigNum::new_zeroed(),
+            minus: false,
+        }
+    }
+
+    #[inline]
+    fn get_digit(&self, pos: usize) -> Self::Digit {
+        self.num.get_digit(pos)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.num.len()
+    }
+}
+
+impl<D> BigNumMut for SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn set_digit(&mut self, pos: usize, digit: Self::Digit) {
+        self.num.set_digit(pos, digit)
+    }
+}
+
+impl<D> DynamicBigNum for SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn resize(&mut self, res: usize) {
+        self.num.resize(res)
+    }
+
+    #[inline]
+    fn new_zeroed_sized(size: usize) -> Self {
+        Self {
+            num: VecBigNum::new_zeroed_sized(size),
+            minus: false,
+        }
+    }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        self.num.reserve(additional)
+    }
+
+    #[inline]
+    fn shrink_to_fit(&mut self) {
+        self.num.shrink_to_fit()
+    }
+
+    #[inline]
+    fn push_digit(&mut self, digit: Self::Digit) {
+        self.num.push_digit(digit)
+    }
+}
+
+impl<D> SignedBigNum for SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn is_minus(&self) -> bool {
+        self.minus
+    }
+}
+
+impl<D> SignedBigNumMut for SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn set_minus(&mut self, minus: bool) {
+        self.minus = minus;
+    }
+}
+
+impl<D> PartialEq for SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<D> Eq for SignedVecBigNum<D> where D: UnsignedNumDigit {}
+
+impl<D> PartialOrd for SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D> Ord for SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (false, false) => SimpleEngine::compare(&self.num, &other.num),
+            (true, true) => SimpleEngine::compare(&other.num, &self.num),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+        }
+    }
+}
+
+impl<D> Hash for SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.is_negative().hash(state);
+        hash_value(&self.num, state)
+    }
+}
+
+impl<D> fmt::Display for SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Formats number in decimal. Negative zero is formatted as zero.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.is_negative(), "", &self.num.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash_of(num: &SignedVecBigNum<u8>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        num.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn num(digits: &[u8], minus: bool) -> SignedVecBigNum<u8> {
+        SignedVecBigNum::from_parts(VecBigNum::from(digits.to_vec()), minus)
+    }
+
+    #[test]
+    fn test_negative_zero() {
+        let zero = num(&[], false);
+        for minus_zero in [num(&[], true), num(&[0, 0], true)] {
+            assert!(minus_zero.is_minus());
+            assert!(!minus_zero.is_negative());
+            assert_eq!(minus_zero, zero);
+            assert_eq!(minus_zero.cmp(&zero), Ordering::Equal);
+            assert_eq!(hash_of(&minus_zero), hash_of(&zero));
+        }
+    }
+
+    #[test]
+    fn test_cmp_mixed_signs() {
+        // sorted by value, magnitudes have different amount of digits and high zeros
+        let sorted = [
+            num(&[0, 1], true),
+            num(&[2, 0, 0], true),
+            num(&[1], true),
+            num(&[], false),
+            num(&[1, 0], false),
+            num(&[2], false),
+            num(&[0, 1, 0], false),
+        ];
+        for (i, a) in sorted.iter().enumerate() {
+            for (j, b) in sorted.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{} cmp {}", a, b);
+                assert_eq!(a.partial_cmp(b), Some(i.cmp(&j)));
+                assert_eq!(a == b, i == j);
+            }
+        }
+
+        assert_eq!(num(&[1], true), num(&[1, 0, 0], true));
+        assert_eq!(hash_of(&num(&[1], true)), hash_of(&num(&[1, 0, 0], true)));
+        assert_ne!(hash_of(&num(&[1], true)), hash_of(&num(&[1], false)));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(num(&[], false).to_string(), "0");
+        assert_eq!(num(&[0, 0], true).to_string(), "0");
+        assert_eq!(num(&[0x0a, 0x01], false).to_string(), "266");
+        assert_eq!(num(&[0x0a, 0x01], true).to_string(), "-266");
+        assert_eq!(format!("{:>6}", num(&[100], true)), "  -100");
+        assert_eq!(format!("{:+}", num(&[7], false)), "+7");
+        assert_eq!(format!("{:+}", num(&[], true)), "+0");
+    }
+}
@@ -1,6 +1,18 @@
-use crate::num::{BigNum, BigNumMut, DefaultBigNumDigit, DynamicBigNum, UnsignedNumDigit};
+use crate::iutil::to_u64;
+use crate::num::{
+    hash_value, BigNum, BigNumMut, DefaultBigNumDigit, DynamicBigNum, UnsignedBigNum,
+    UnsignedNumDigit,
+};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
 use core::ops::{Index, IndexMut};
 
+/// Heap allocated bignum, which can be resized.
+///
+/// Equality, ordering and hashing are value based: high zero digits are ignored,
+/// so it can be compared with any other `UnsignedBigNum` with the same digit type.
 #[derive(Debug, Clone)]
 pub struct VecBigNum<D = DefaultBigNumDigit> {
     num: Vec<D>,
@@ -18,9 +30,9 @@ impl<D> From<Vec<D>> for VecBigNum<D> {
     }
 }
 
-impl<D> Into<Vec<D>> for VecBigNum<D> {
-    fn into(self) -> Vec<D> {
-        self.num
+impl<D> From<VecBigNum<D>> for Vec<D> {
+    fn from(num: VecBigNum<D>) -> Self {
+        num.num
     }
 }
 
@@ -72,6 +84,8 @@ where
     }
 }
 
+impl<D> UnsignedBigNum for VecBigNum<D> where D: UnsignedNumDigit {}
+
 impl<D> DynamicBigNum for VecBigNum<D>
 where
     D: UnsignedNumDigit,
@@ -87,4 +101,214 @@ where
         num.resize(size, D::default());
         Self { num }
     }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        self.num.reserve(additional)
+    }
+
+    #[inline]
+    fn shrink_to_fit(&mut self) {
+        self.num.shrink_to_fit()
+    }
+
+    #[inline]
+    fn push_digit(&mut self, digit: Self::Digit) {
+        self.num.push(digit)
+    }
+}
+
+impl<D, T> PartialEq<T> for VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+    T: UnsignedBigNum<Digit = D>,
+{
+    #[inline]
+    fn eq(&self, other: &T) -> bool {
+        SimpleEngine::compare(self, other) == Ordering::Equal
+    }
+}
+
+impl<D> Eq for VecBigNum<D> where D: UnsignedNumDigit {}
+
+impl<D, T> PartialOrd<T> for VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+    T: UnsignedBigNum<Digit = D>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        Some(SimpleEngine::compare(self, other))
+    }
+}
+
+impl<D> Ord for VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        SimpleEngine::compare(self, other)
+    }
+}
+
+impl<D> Hash for VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(self, state)
+    }
+}
+
+impl<D> fmt::Display for VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Formats number in decimal.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // number is divided by the biggest power of ten, which fits both digit and u64
+        let chunk_len = if D::NUM_BITS >= 64 {
+            19
+        } else {
+            D::NUM_BITS as usize * 3 / 10
+        };
+        let divisor = D::try_from(10u64.pow(chunk_len as u32)).ok().unwrap();
+
+        let mut num = self.clone();
+        let mut chunks = Vec::new();
+        while !num.is_zero() {
+            let chunk = SimpleEngine::div_rem_digit(&mut num, divisor);
+            chunks.push(to_u64(&VecBigNum::from(vec![chunk])));
+            num.normalize();
+        }
+
+        let mut text = match chunks.pop() {
+            Some(top) => top.to_string(),
+            None => String::from("0"),
+        };
+        for chunk in chunks.iter().rev() {
+            text.push_str(&format!("{:01$}", chunk, chunk_len));
+        }
+        f.pad_integral(true, "", &text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::num::ArrayBigNum;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn hash_of(num: &VecBigNum<u8>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        num.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_eq_ignores_high_zeros() {
+        let a = VecBigNum::from(vec![1u8]);
+        let b = VecBigNum::from(vec![1u8, 0, 0]);
+        let empty = VecBigNum::<u8>::from(vec![]);
+        let zero = VecBigNum::from(vec![0u8, 0]);
+
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(empty, zero);
+        assert_eq!(hash_of(&empty), hash_of(&zero));
+        assert_ne!(a, zero);
+    }
+
+    #[test]
+    fn test_cmp_u16() {
+        for a in (0..u16::MAX).step_by(251) {
+            for b in (0..u16::MAX).step_by(241) {
+                let bn_a = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
+                let mut bn_b = VecBigNum::from(Vec::from(&b.to_le_bytes()[..]));
+                bn_b.resize(4);
+
+                assert_eq!(bn_a.partial_cmp(&bn_b), Some(a.cmp(&b)));
+                assert_eq!(bn_b.cmp(&bn_b.clone()), Ordering::Equal);
+                assert_eq!(a == b, bn_a == bn_b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cmp_array() {
+        let a = VecBigNum::from(vec![1u8, 2]);
+        let b = ArrayBigNum::from([1u8, 2, 0, 0]);
+        let c = ArrayBigNum::from([2u8, 2, 0, 0]);
+
+        assert!(a == b);
+        assert!(b == a);
+        assert!(a < c);
+        assert!(c > a);
+    }
+
+    #[test]
+    fn test_as_map_key() {
+        let mut hash_map = HashMap::new();
+        let mut btree_map = BTreeMap::new();
+        for i in 0..10u8 {
+            hash_map.insert(VecBigNum::from(vec![i]), i);
+            btree_map.insert(VecBigNum::from(vec![i]), i);
+        }
+
+        for i in 0..10u8 {
+            let key = VecBigNum::from(vec![i, 0, 0, 0]);
+            assert_eq!(hash_map.get(&key), Some(&i));
+            assert_eq!(btree_map.get(&key), Some(&i));
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(VecBigNum::<u8>::from(vec![]).to_string(), "0");
+        assert_eq!(VecBigNum::from(vec![0u8, 0]).to_string(), "0");
+        assert_eq!(VecBigNum::from(vec![0x0au8, 0x01]).to_string(), "266");
+        assert_eq!(
+            VecBigNum::from(vec![0u32, 0, 0, 1]).to_string(),
+            "79228162514264337593543950336"
+        );
+        assert_eq!(
+            VecBigNum::from(vec![u128::MAX, 1]).to_string(),
+            "680564733841876926926749214863536422911"
+        );
+        assert_eq!(format!("{:>6}", VecBigNum::from(vec![100u16])), "   100");
+        assert_eq!(format!("{:+}", VecBigNum::from(vec![7u64])), "+7");
+    }
+
+    #[test]
+    fn test_normalize_and_capacity() {
+        let mut num = VecBigNum::from(vec![1u8, 0, 2, 0, 0]);
+        assert_eq!(num.significant_len(), 3);
+        assert!(!num.is_zero());
+        assert!(!num.is_one());
+
+        num.normalize();
+        assert_eq!(num.len(), 3);
+        num.truncate(5);
+        assert_eq!(num.len(), 3);
+        num.truncate(1);
+        assert!(num.is_one());
+
+        num.reserve(10);
+        num.push_digit(0);
+        num.push_digit(7);
+        assert_eq!(num.clone().into_inner(), vec![1, 0, 7]);
+        num.shrink_to_fit();
+
+        let mut zero = VecBigNum::from(vec![0u8, 0]);
+        assert!(zero.is_zero());
+        zero.normalize();
+        assert!(zero.is_empty());
+        assert!(zero.is_zero());
+
+        let mut num = VecBigNum::from(vec![0u8, 3, 0]);
+        num.trim();
+        assert_eq!(num.into_inner(), vec![0, 3]);
+    }
 }
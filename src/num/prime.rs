@@ -0,0 +1,759 @@
+use crate::iutil::{from_u64, to_u64};
+use crate::num::{BigNum, BigNumBits, DynamicBigNum, UnsignedBigNum, UnsignedNumDigit, VecBigNum};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+#[cfg(feature = "rand")]
+use {
+    crate::num::{BigNumBitsMut, BigNumRandom},
+    rand_core::RngCore,
+};
+
+/// Primes used for trial division, before any expensive test is done.
+pub(crate) const SMALL_PRIMES: &[u16] = &[
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251, 257, 263, 269, 271, 277, 281, 283, 293, 307,
+    311, 313, 317, 331, 337, 347, 349, 353, 359, 367, 373, 379, 383, 389, 397, 401, 409, 419, 421,
+    431, 433, 439, 443, 449, 457, 461, 463, 467, 479, 487, 491, 499, 503, 509, 521, 523, 541, 547,
+    557, 563, 569, 571, 577, 587, 593, 599, 601, 607, 613, 617, 619, 631, 641, 643, 647, 653, 659,
+    661, 673, 677, 683, 691, 701, 709, 719, 727, 733, 739, 743, 751, 757, 761, 769, 773, 787, 797,
+    809, 811, 821, 823, 827, 829, 839, 853, 857, 859, 863, 877, 881, 883, 887, 907, 911, 919, 929,
+    937, 941, 947, 953, 967, 971, 977, 983, 991, 997,
+];
+
+/// Primality testing of bignums.
+///
+/// It's only implemented for unsigned bignums, since digits of signed numbers are not their value.
+///
+/// ```compile_fail
+/// use varnum::num::{BigNumPrime, Int};
+///
+/// // digits of -3 are 253 = 11 * 23
+/// Int::<u8, 1>::from(-3i8).is_probable_prime(10);
+/// ```
+///
+/// ```compile_fail
+/// use varnum::num::{BigNumPrime, SignedVecBigNum, VecBigNum};
+///
+/// SignedVecBigNum::from_parts(VecBigNum::from(vec![3u8]), true).is_probable_prime(10);
+/// ```
+pub trait BigNumPrime: BigNum {
+    /// Returns true if number is probably prime.
+    ///
+    /// Number is trial divided by small primes first.
+    /// Then Baillie-PSW test is done, which is Miller-Rabin test with base 2 followed by strong Lucas test.
+    /// No composite number, which passes it, is known.
+    /// After that `rounds` Miller-Rabin tests are done with consecutive bases starting at 3,
+    /// so result is deterministic.
+    ///
+    /// Since bases of these rounds are known in advance, composite number can be crafted to pass them,
+    /// so they are not sound against adversary. Use `is_probable_prime_with_rng` to validate numbers
+    /// received from untrusted parties.
+    fn is_probable_prime(&self, rounds: usize) -> bool;
+
+    /// Works just like `is_probable_prime`, but bases of additional Miller-Rabin rounds are chosen uniformly
+    /// from range `[2, n - 2]`, so each round passes composite number with probability at most 1/4,
+    /// even if the number was chosen by adversary.
+    #[cfg(feature = "rand")]
+    fn is_probable_prime_with_rng<R>(&self, rounds: usize, rng: &mut R) -> bool
+    where
+        R: RngCore + ?Sized;
+}
+
+/// Runs primality test of `num` with additional Miller-Rabin rounds with bases returned by `base`.
+/// `base` gets `n - 1` and round index and returns `None` if there are no more bases to try.
+fn probable_prime<T>(
+    num: &T,
+    rounds: usize,
+    mut base: impl FnMut(&VecBigNum<T::Digit>, usize) -> Option<VecBigNum<T::Digit>>,
+) -> bool
+where
+    T: BigNum,
+{
+    let mut n = VecBigNum::<T::Digit>::new_zeroed_sized(num.significant_len());
+    for i in 0..n.len() {
+        n[i] = num[i];
+    }
+
+    if let Some(res) = trial_division(&n) {
+        return res;
+    }
+
+    let one = from_u64::<T::Digit>(1);
+    let mut n_minus_one = n.clone();
+    SimpleEngine::sub_resize(&mut n_minus_one, &one);
+
+    if !miller_rabin(&n, &n_minus_one, &from_u64(2)) || !strong_lucas(&n) {
+        return false;
+    }
+
+    for i in 0..rounds {
+        let base = match base(&n_minus_one, i) {
+            Some(base) => base,
+            None => break,
+        };
+        if !miller_rabin(&n, &n_minus_one, &base) {
+            return false;
+        }
+    }
+    true
+}
+
+impl<T> BigNumPrime for T
+where
+    T: UnsignedBigNum,
+{
+    fn is_probable_prime(&self, rounds: usize) -> bool {
+        probable_prime(self, rounds, |n_minus_one, i| {
+            let base = from_u64(i as u64 + 3);
+            // bases must be smaller than n - 1
+            if SimpleEngine::compare(&base, n_minus_one) == Ordering::Less {
+                Some(base)
+            } else {
+                None
+            }
+        })
+    }
+
+    #[cfg(feature = "rand")]
+    fn is_probable_prime_with_rng<R>(&self, rounds: usize, rng: &mut R) -> bool
+    where
+        R: RngCore + ?Sized,
+    {
+        // numbers, which got past trial division, are bigger than 3, so the range is not empty
+        let two = from_u64(2);
+        probable_prime(self, rounds, |n_minus_one, _| {
+            Some(VecBigNum::random_range(&two, n_minus_one, rng))
+        })
+    }
+}
+
+/// Returns remainder of `n` divided by small number `d`.
+fn rem_small<D>(n: &VecBigNum<D>, d: u16) -> u64
+where
+    D: UnsignedNumDigit,
+{
+    match D::try_from(d) {
+        Ok(digit) => {
+            // remainder is smaller than d, so it fits single digit
+            let rem = VecBigNum::from(vec![SimpleEngine::rem_digit(n, digit)]);
+            to_u64(&rem)
+        }
+        Err(_) => {
+            let mut quot = VecBigNum::new_zeroed();
+            let mut rem = VecBigNum::new_zeroed();
+            SimpleEngine::div_rem_resize(&mut quot, &mut rem, n, &from_u64(d as u64));
+            to_u64(&rem)
+        }
+    }
+}
+
+/// Returns result of primality test if trial division is enough to determine it.
+pub(crate) fn trial_division<D>(n: &VecBigNum<D>) -> Option<bool>
+where
+    D: UnsignedNumDigit,
+{
+    if SimpleEngine::compare_u64(n, 2) == Ordering::Less {
+        return Some(false);
+    }
+
+    for &p in SMALL_PRIMES {
+        if SimpleEngine::compare_u64(n, p as u64) == Ordering::Equal {
+            return Some(true);
+        }
+        if rem_small(n, p) == 0 {
+            return Some(false);
+        }
+    }
+
+    // number without divisor smaller than its square root is prime
+    let largest = *SMALL_PRIMES.last().unwrap() as u64;
+    if SimpleEngine::compare_u64(n, largest * largest) == Ordering::Less {
+        return Some(true);
+    }
+    None
+}
+
+fn mod_mul<D>(lhs: &VecBigNum<D>, rhs: &VecBigNum<D>, n: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut prod = VecBigNum::new_zeroed();
+    SimpleEngine::mul_resize(&mut prod, lhs, rhs);
+    let mut quot = VecBigNum::new_zeroed();
+    let mut rem = VecBigNum::new_zeroed();
+    SimpleEngine::div_rem_resize(&mut quot, &mut rem, &prod, n);
+    rem
+}
+
+/// Adds two numbers smaller than `n` modulo `n`.
+fn mod_add<D>(lhs: &VecBigNum<D>, rhs: &VecBigNum<D>, n: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = lhs.clone();
+    SimpleEngine::add_resize(&mut res, rhs);
+    if SimpleEngine::compare(&res, n) != Ordering::Less {
+        SimpleEngine::sub_resize(&mut res, n);
+    }
+    res
+}
+
+/// Subtracts two numbers smaller than `n` modulo `n`.
+fn mod_sub<D>(lhs: &VecBigNum<D>, rhs: &VecBigNum<D>, n: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = lhs.clone();
+    if SimpleEngine::compare(lhs, rhs) == Ordering::Less {
+        SimpleEngine::add_resize(&mut res, n);
+    }
+    SimpleEngine::sub_resize(&mut res, rhs);
+    res
+}
+
+/// Divides number smaller than odd `n` by two modulo `n`.
+fn mod_half<D>(num: &VecBigNum<D>, n: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = num.clone();
+    if res.test_bit(0) {
+        SimpleEngine::add_resize(&mut res, n);
+    }
+    SimpleEngine::shift_right_u32(&mut res, 1);
+    res
+}
+
+/// Returns residue of signed small number modulo `n`.
+fn residue<D>(abs: u64, negative: bool, n: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut quot = VecBigNum::new_zeroed();
+    let mut rem = VecBigNum::new_zeroed();
+    SimpleEngine::div_rem_resize(&mut quot, &mut rem, &from_u64(abs), n);
+    if negative && !rem.is_zero() {
+        let mut res = n.clone();
+        SimpleEngine::sub_resize(&mut res, &rem);
+        res
+    } else {
+        rem
+    }
+}
+
+/// Miller-Rabin test of odd `n` with single base.
+fn miller_rabin<D>(n: &VecBigNum<D>, n_minus_one: &VecBigNum<D>, base: &VecBigNum<D>) -> bool
+where
+    D: UnsignedNumDigit,
+{
+    let s = BigNumBits::trailing_zeros(n_minus_one);
+    let mut d = n_minus_one.clone();
+    SimpleEngine::shift_right_usize(&mut d, s);
+
+    let mut x = VecBigNum::new_zeroed();
+    SimpleEngine::pow_mod_resize(&mut x, base, &d, n);
+    if x.is_one() || SimpleEngine::compare(&x, n_minus_one) == Ordering::Equal {
+        return true;
+    }
+    for _ in 1..s {
+        x = mod_mul(&x, &x, n);
+        if SimpleEngine::compare(&x, n_minus_one) == Ordering::Equal {
+            return true;
+        }
+    }
+    false
+}
+
+/// Computes Jacobi symbol `(a / n)` for odd `n`.
+pub(crate) fn jacobi<D>(a: &VecBigNum<D>, n: &VecBigNum<D>) -> i32
+where
+    D: UnsignedNumDigit,
+{
+    let mut quot = VecBigNum::new_zeroed();
+    let mut a_rem = VecBigNum::new_zeroed();
+    SimpleEngine::div_rem_resize(&mut quot, &mut a_rem, a, n);
+
+    let mut a = a_rem;
+    let mut n = n.clone();
+    let mut res = 1;
+    while !a.is_zero() {
+        let zeros = BigNumBits::trailing_zeros(&a);
+        SimpleEngine::shift_right_usize(&mut a, zeros);
+        let n_mod_8 = rem_small(&n, 8);
+        if zeros % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+            res = -res;
+        }
+
+        core::mem::swap(&mut a, &mut n);
+        if rem_small(&a, 4) == 3 && rem_small(&n, 4) == 3 {
+            res = -res;
+        }
+
+        let mut quot = VecBigNum::new_zeroed();
+        let mut rem = VecBigNum::new_zeroed();
+        SimpleEngine::div_rem_resize(&mut quot, &mut rem, &a, &n);
+        a = rem;
+    }
+
+    if n.is_one() {
+        res
+    } else {
+        0
+    }
+}
+
+/// Returns true if number is perfect square.
+fn is_square<D>(n: &VecBigNum<D>) -> bool
+where
+    D: UnsignedNumDigit,
+{
+    if n.is_zero() {
+        return true;
+    }
+
+    // Newton's method starting with value not smaller than square root
+    let mut x = from_u64(1);
+    SimpleEngine::shift_left_resize(&mut x, (n.bit_len() as u64 + 1) / 2);
+    loop {
+        let mut quot = VecBigNum::new_zeroed();
+        let mut rem = VecBigNum::new_zeroed();
+        SimpleEngine::div_rem_resize(&mut quot, &mut rem, n, &x);
+        SimpleEngine::add_resize(&mut quot, &x);
+        SimpleEngine::shift_right_u32(&mut quot, 1);
+        if SimpleEngine::compare(&quot, &x) != Ordering::Less {
+            break;
+        }
+        x = quot;
+    }
+
+    let mut square = VecBigNum::new_zeroed();
+    SimpleEngine::mul_resize(&mut square, &x, &x);
+    SimpleEngine::compare(&square, n) == Ordering::Equal
+}
+
+/// Strong Lucas probable prime test of odd `n` with parameters chosen by Selfridge's method.
+pub(crate) fn strong_lucas<D>(n: &VecBigNum<D>) -> bool
+where
+    D: UnsignedNumDigit,
+{
+    // there is no suitable parameter for perfect squares
+    if is_square(n) {
+        return false;
+    }
+
+    // first of 5, -7, 9, -11, ... for which Jacobi symbol is -1
+    let mut d_abs = 5u64;
+    let mut d_negative = false;
+    loop {
+        let d = residue(d_abs, d_negative, n);
+        match jacobi(&d, n) {
+            -1 => break,
+            // n has common factor with d, so unless it's d itself it's composite
+            0 if SimpleEngine::compare_u64(n, d_abs) != Ordering::Equal => return false,
+            _ => {}
+        }
+        d_abs += 2;
+        d_negative = !d_negative;
+    }
+
+    // P = 1, Q = (1 - D) / 4
+    let d = residue(d_abs, d_negative, n);
+    let q = if d_negative {
+        residue((d_abs + 1) / 4, false, n)
+    } else {
+        residue((d_abs - 1) / 4, true, n)
+    };
+
+    // n + 1 = k * 2^s, where k is odd
+    let mut k = n.clone();
+    SimpleEngine::add_resize(&mut k, &from_u64(1));
+    let s = BigNumBits::trailing_zeros(&k);
+    SimpleEngine::shift_right_usize(&mut k, s);
+
+    // U_1 = 1, V_1 = P, Q^1 = Q
+    let mut u = from_u64(1);
+    let mut v = from_u64(1);
+    let mut qk = q.clone();
+    for bit in (0..k.bit_len() - 1).rev() {
+        // U_2k = U_k * V_k, V_2k = V_k^2 - 2 * Q^k
+        u = mod_mul(&u, &v, n);
+        v = mod_sub(&mod_mul(&v, &v, n), &mod_add(&qk, &qk, n), n);
+        qk = mod_mul(&qk, &qk, n);
+
+        if k.test_bit(bit) {
+            // U_k+1 = (P * U_k + V_k) / 2, V_k+1 = (D * U_k + P * V_k) / 2
+            let next_u = mod_half(&mod_add(&u, &v, n), n);
+            let next_v = mod_half(&mod_add(&mod_mul(&d, &u, n), &v, n), n);
+            u = next_u;
+            v = next_v;
+            qk = mod_mul(&qk, &q, n);
+        }
+    }
+
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = mod_sub(&mod_mul(&v, &v, n), &mod_add(&qk, &qk, n), n);
+        qk = mod_mul(&qk, &qk, n);
+        if v.is_zero() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Amount of numbers above random start, which are sieved before new start is chosen.
+#[cfg(feature = "rand")]
+const SIEVE_WINDOW: u64 = 1 << 14;
+
+/// Numbers smaller than that are not sieved, since sieve would reject small primes themselves.
+#[cfg(feature = "rand")]
+const SIEVE_MIN_BITS: usize = 16;
+
+/// Returns random odd number with exactly `bits` bits.
+/// For big enough numbers two most significant bits are set.
+#[cfg(feature = "rand")]
+fn random_candidate<D, R>(bits: usize, rng: &mut R) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+    R: RngCore + ?Sized,
+{
+    let mut res = VecBigNum::random_bits(bits, rng);
+    res.set_bit(bits - 1);
+    if bits >= SIEVE_MIN_BITS {
+        res.set_bit(bits - 2);
+    }
+    res.set_bit(0);
+    res
+}
+
+/// Returns true if `n` is prime and, if `safe` is set, `2n + 1` is prime too.
+#[cfg(feature = "rand")]
+fn is_candidate_prime<D>(n: &VecBigNum<D>, safe: bool) -> bool
+where
+    D: UnsignedNumDigit,
+{
+    if !n.is_probable_prime(0) {
+        return false;
+    }
+    if safe {
+        let mut p = n.clone();
+        SimpleEngine::shift_left_resize(&mut p, 1);
+        SimpleEngine::add_resize(&mut p, &from_u64(1));
+        return p.is_probable_prime(0);
+    }
+    true
+}
+
+/// Searches odd numbers in window above `start` using incremental sieve.
+/// Remainders of `start` modulo small primes are computed once, so candidates with small factor
+/// are rejected without any bignum operation.
+#[cfg(feature = "rand")]
+fn search_window<D>(start: &VecBigNum<D>, bits: usize, safe: bool) -> Option<VecBigNum<D>>
+where
+    D: UnsignedNumDigit,
+{
+    // start is odd, so there is no need to sieve with two
+    let primes = &SMALL_PRIMES[1..];
+    let residues: Vec<u64> = primes.iter().map(|&p| rem_small(start, p)).collect();
+
+    let mut delta = 0;
+    while delta < SIEVE_WINDOW {
+        let sieved = primes.iter().zip(residues.iter()).any(|(&p, &r)| {
+            let p = p as u64;
+            let r = (r + delta) % p;
+            r == 0 || (safe && (2 * r + 1) % p == 0)
+        });
+
+        if !sieved {
+            let mut candidate = start.clone();
+            SimpleEngine::add_resize(&mut candidate, &from_u64(delta));
+            if candidate.bit_len() > bits {
+                return None;
+            }
+            if is_candidate_prime(&candidate, safe) {
+                return Some(candidate);
+            }
+        }
+        delta += 2;
+    }
+    None
+}
+
+#[cfg(feature = "rand")]
+fn generate<D, R>(bits: usize, safe: bool, rng: &mut R) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+    R: RngCore + ?Sized,
+{
+    loop {
+        let start = random_candidate(bits, rng);
+        let found = if bits < SIEVE_MIN_BITS {
+            Some(start).filter(|n| is_candidate_prime(n, safe))
+        } else {
+            search_window(&start, bits, safe)
+        };
+        if let Some(mut res) = found {
+            res.normalize();
+            return res;
+        }
+    }
+}
+
+/// Generates random prime with exactly `bits` bits.
+///
+/// Random odd start is chosen and window above it is sieved with small primes,
+/// then remaining candidates are checked with `is_probable_prime`.
+/// Primes with at least 16 bits have two most significant bits set,
+/// so product of two such primes has exactly `2 * bits` bits.
+///
+/// # Panic
+/// Panics when `bits` is smaller than 2.
+#[cfg(feature = "rand")]
+pub fn generate_prime<D, R>(bits: usize, rng: &mut R) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+    R: RngCore + ?Sized,
+{
+    assert!(bits >= 2, "There are no odd primes with less than 2 bits");
+    generate(bits, false, rng)
+}
+
+/// Generates random safe prime `p = 2q + 1`, where `q` is prime too, with exactly `bits` bits.
+/// Both `p` and `q` are sieved at once, so candidates with small factor in either are rejected cheaply.
+///
+/// # Panic
+/// Panics when `bits` is smaller than 3.
+#[cfg(feature = "rand")]
+pub fn generate_safe_prime<D, R>(bits: usize, rng: &mut R) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+    R: RngCore + ?Sized,
+{
+    assert!(bits >= 3, "There are no safe primes with less than 3 bits");
+    let q = generate::<D, R>(bits - 1, true, rng);
+
+    let mut p = q;
+    SimpleEngine::shift_left_resize(&mut p, 1);
+    SimpleEngine::add_resize(&mut p, &from_u64(1));
+    p.normalize();
+    p
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::iutil::xorshift;
+    use crate::num::ArrayBigNum;
+
+    /// Deterministic Miller-Rabin for u64, which is used as reference.
+    fn is_prime_u64(n: u64) -> bool {
+        if n < 2 {
+            return false;
+        }
+        for &p in &[2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+            if n % p == 0 {
+                return n == p;
+            }
+        }
+        let mul = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+        let pow = |mut b: u64, mut e: u64| {
+            let mut res = 1;
+            while e > 0 {
+                if e & 1 == 1 {
+                    res = mul(res, b);
+                }
+                b = mul(b, b);
+                e >>= 1;
+            }
+            res
+        };
+        let s = (n - 1).trailing_zeros();
+        let d = (n - 1) >> s;
+        'bases: for &a in &[2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+            let mut x = pow(a, d);
+            if x == 1 || x == n - 1 {
+                continue;
+            }
+            for _ in 1..s {
+                x = mul(x, x);
+                if x == n - 1 {
+                    continue 'bases;
+                }
+            }
+            return false;
+        }
+        true
+    }
+
+    #[test]
+    fn test_small_numbers() {
+        for n in 0..3000u64 {
+            assert_eq!(
+                from_u64::<u8>(n).is_probable_prime(0),
+                is_prime_u64(n),
+                "{}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_u64_numbers() {
+        let mut state = 0x0123_4567_89ab_cdefu64;
+        for _ in 0..2000 {
+            let n = (xorshift(&mut state) >> (state % 40)) | 1;
+            assert_eq!(
+                from_u64::<u32>(n).is_probable_prime(2),
+                is_prime_u64(n),
+                "{}",
+                n
+            );
+            assert_eq!(
+                from_u64::<u8>(n).is_probable_prime(0),
+                is_prime_u64(n),
+                "{}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_array_numbers() {
+        // high zero digits don't change the value
+        for &(digits, prime) in &[
+            ([3u8, 0, 0], true),
+            ([253, 0, 0], false),
+            ([3, 1, 0], false),
+        ] {
+            assert_eq!(ArrayBigNum::from(digits).is_probable_prime(3), prime);
+            assert_eq!(VecBigNum::from(digits.to_vec()).is_probable_prime(3), prime);
+        }
+    }
+
+    #[test]
+    fn test_pseudoprimes() {
+        // Carmichael numbers and strong pseudoprimes to many bases
+        for &n in &[
+            561u64,
+            41041,
+            825_265,
+            2047,
+            3_215_031_751,
+            3_825_123_056_546_413_051,
+            // strong Lucas pseudoprimes
+            5459,
+            5777,
+            10877,
+        ] {
+            assert!(!from_u64::<u32>(n).is_probable_prime(5), "{}", n);
+        }
+
+        // strong Lucas test on its own accepts its pseudoprimes
+        for &n in &[5459u64, 5777, 10877, 16109, 18971] {
+            assert!(strong_lucas(&from_u64::<u32>(n)), "{}", n);
+        }
+        assert!(!strong_lucas(&from_u64::<u32>(5781)));
+    }
+
+    #[test]
+    fn test_mersenne_numbers() {
+        for &(exp, prime) in &[
+            (61, true),
+            (67, false),
+            (89, true),
+            (107, true),
+            (127, true),
+            (128, false),
+        ] {
+            let mut n = from_u64::<u32>(1);
+            SimpleEngine::shift_left_resize(&mut n, exp);
+            SimpleEngine::sub_resize(&mut n, &from_u64(1));
+            assert_eq!(n.is_probable_prime(3), prime, "2^{} - 1", exp);
+        }
+    }
+
+    #[test]
+    fn test_jacobi() {
+        // values from table of Jacobi symbols
+        let table: &[(u64, u64, i32)] = &[
+            (1, 1, 1),
+            (2, 3, -1),
+            (2, 15, 1),
+            (7, 15, -1),
+            (5, 21, 1),
+            (6, 9, 0),
+            (30, 59, -1),
+        ];
+        for &(a, n, expected) in table {
+            assert_eq!(
+                jacobi(&from_u64::<u8>(a), &from_u64(n)),
+                expected,
+                "({} / {})",
+                a,
+                n
+            );
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random_bases() {
+        use crate::num::random::test::SplitMix;
+
+        let mut rng = SplitMix(3);
+        for n in (0..3000u64).chain([3_215_031_751, 3_825_123_056_546_413_051]) {
+            assert_eq!(
+                from_u64::<u16>(n).is_probable_prime_with_rng(4, &mut rng),
+                is_prime_u64(n),
+                "{}",
+                n
+            );
+        }
+
+        let mut n = from_u64::<u32>(1);
+        SimpleEngine::shift_left_resize(&mut n, 127);
+        SimpleEngine::sub_resize(&mut n, &from_u64(1));
+        assert!(n.is_probable_prime_with_rng(10, &mut rng));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_generate_prime() {
+        use crate::num::random::test::SplitMix;
+
+        let mut rng = SplitMix(42);
+        for bits in 2..20 {
+            let p = generate_prime::<u8, _>(bits, &mut rng);
+            assert_eq!(p.bit_len(), bits);
+            assert!(is_prime_u64(to_u64(&p)), "{:?}", p);
+        }
+        for &bits in &[64, 128, 256] {
+            let p = generate_prime::<u32, _>(bits, &mut rng);
+            assert_eq!(p.bit_len(), bits);
+            assert!(p.test_bit(bits - 2));
+            assert!(p.is_probable_prime(5));
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_generate_safe_prime() {
+        use crate::num::random::test::SplitMix;
+
+        let mut rng = SplitMix(7);
+        for bits in 3..20 {
+            let p = to_u64(&generate_safe_prime::<u16, _>(bits, &mut rng));
+            assert_eq!(64 - p.leading_zeros() as usize, bits);
+            assert!(is_prime_u64(p) && is_prime_u64(p / 2), "{}", p);
+        }
+
+        let p = generate_safe_prime::<u32, _>(128, &mut rng);
+        assert_eq!(p.bit_len(), 128);
+        let mut q = p.clone();
+        SimpleEngine::shift_right_u32(&mut q, 1);
+        assert!(p.is_probable_prime(5) && q.is_probable_prime(5));
+    }
+}
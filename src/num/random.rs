@@ -0,0 +1,211 @@
+use crate::num::{
+    ArrayBigNum, BigNum, BigNumBits, BigNumMut, DynamicBigNum, UnsignedNumDigit, VecBigNum,
+};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+use rand_core::RngCore;
+
+/// Returns random digit, which has `bits` lowest bits random and other ones cleared.
+fn random_digit<D, R>(bits: u32, rng: &mut R) -> D
+where
+    D: UnsignedNumDigit,
+    R: RngCore + ?Sized,
+{
+    let mut res = D::ZERO;
+    let mut pos = 0;
+    while pos < bits {
+        let chunk_bits = (bits - pos).min(32);
+        let chunk = if chunk_bits == 32 {
+            rng.next_u32()
+        } else {
+            rng.next_u32() & ((1 << chunk_bits) - 1)
+        };
+        // chunk has no more bits than digit has left
+        res |= D::try_from(chunk).ok().unwrap() << pos;
+        pos += chunk_bits;
+    }
+    res
+}
+
+/// Fills `bits` least significant bits of `num` with random ones and clears other bits.
+fn fill_random_bits<N, R>(num: &mut N, bits: usize, rng: &mut R)
+where
+    N: BigNumMut,
+    R: RngCore + ?Sized,
+{
+    let digit_bits = N::Digit::NUM_BITS as usize;
+    for i in 0..num.len() {
+        let rest = bits.saturating_sub(i * digit_bits).min(digit_bits);
+        num[i] = random_digit(rest as u32, rng);
+    }
+}
+
+/// Generation of uniformly distributed random bignums.
+pub trait BigNumRandom: BigNumMut {
+    /// Returns number with `bits` random bits, so it's uniformly distributed in range `[0, 2^bits)`.
+    ///
+    /// # Panic
+    /// Panics when number of fixed size can't store `bits` bits.
+    fn random_bits<R>(bits: usize, rng: &mut R) -> Self
+    where
+        R: RngCore + ?Sized;
+
+    /// Returns number uniformly distributed in range `[0, bound)`.
+    /// Rejection sampling is used, so there is no modulo bias.
+    ///
+    /// # Panic
+    /// Panics when `bound` is zero.
+    fn random_below<R>(bound: &Self, rng: &mut R) -> Self
+    where
+        R: RngCore + ?Sized,
+    {
+        assert!(!bound.is_zero(), "Bound must not be zero");
+
+        let bits = bound.bit_len();
+        let mut res = bound.clone();
+        loop {
+            // bound has the same amount of bits, so at least half of values are accepted
+            fill_random_bits(&mut res, bits, rng);
+            if SimpleEngine::compare(&res, bound) == Ordering::Less {
+                return res;
+            }
+        }
+    }
+
+    /// Returns number uniformly distributed in range `[lo, hi)`.
+    ///
+    /// # Panic
+    /// Panics when range is empty.
+    fn random_range<R>(lo: &Self, hi: &Self, rng: &mut R) -> Self
+    where
+        R: RngCore + ?Sized,
+    {
+        assert!(
+            SimpleEngine::compare(lo, hi) == Ordering::Less,
+            "Range must not be empty"
+        );
+
+        let mut size = hi.clone();
+        let borrow = SimpleEngine::sub_accumulate(&mut size, lo);
+        debug_assert!(!borrow);
+
+        // result is smaller than hi, so it fits digits of hi
+        let mut res = Self::random_below(&size, rng);
+        let carry = SimpleEngine::add_accumulate(&mut res, lo);
+        debug_assert!(!carry);
+        res
+    }
+}
+
+impl<D> BigNumRandom for VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn random_bits<R>(bits: usize, rng: &mut R) -> Self
+    where
+        R: RngCore + ?Sized,
+    {
+        let mut res =
+            Self::new_zeroed_sized((bits + D::NUM_BITS as usize - 1) / D::NUM_BITS as usize);
+        fill_random_bits(&mut res, bits, rng);
+        res
+    }
+}
+
+impl<D, const N: usize> BigNumRandom for ArrayBigNum<D, N>
+where
+    D: UnsignedNumDigit,
+{
+    fn random_bits<R>(bits: usize, rng: &mut R) -> Self
+    where
+        R: RngCore + ?Sized,
+    {
+        assert!(
+            bits <= N * D::NUM_BITS as usize,
+            "Number can't store that many bits"
+        );
+        let mut res = Self::new_zeroed();
+        fill_random_bits(&mut res, bits, rng);
+        res
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+
+    /// Simple splitmix generator, so tests do not depend on generator crates.
+    pub(crate) struct SplitMix(pub u64);
+
+    impl RngCore for SplitMix {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            rand_core::impls::fill_bytes_via_next(self, dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_random_bits() {
+        let mut rng = SplitMix(0x1234_5678_9abc_def0);
+        for bits in 0..200 {
+            let num = VecBigNum::<u32>::random_bits(bits, &mut rng);
+            assert!(num.bit_len() <= bits);
+
+            let num = VecBigNum::<u128>::random_bits(bits, &mut rng);
+            assert!(num.bit_len() <= bits);
+
+            let num = ArrayBigNum::<u8, 25>::random_bits(bits, &mut rng);
+            assert!(num.bit_len() <= bits);
+        }
+
+        // top bit is set in about half of cases
+        let set = (0..1000)
+            .filter(|_| VecBigNum::<u8>::random_bits(13, &mut rng).test_bit(12))
+            .count();
+        assert!((400..600).contains(&set));
+    }
+
+    #[test]
+    fn test_random_below_and_range() {
+        let mut rng = SplitMix(0xdead_beef_cafe_babe);
+
+        // every value below small bound is generated
+        let bound = VecBigNum::from(vec![0x2au8, 0x01]);
+        let mut seen = [false; 0x12a];
+        for _ in 0..10000 {
+            let num = VecBigNum::random_below(&bound, &mut rng);
+            assert!(num < bound);
+            seen[num[0] as usize | (num[1] as usize) << 8] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        let lo = ArrayBigNum::from([0xffu8, 0xff, 0, 0]);
+        let hi = ArrayBigNum::from([0x05u8, 0x00, 0x01, 0]);
+        for _ in 0..1000 {
+            let num = ArrayBigNum::random_range(&lo, &hi, &mut rng);
+            assert!(num >= lo && num < hi);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_random_bits_do_not_fit() {
+        ArrayBigNum::<u8, 2>::random_bits(17, &mut SplitMix(1));
+    }
+}
@@ -0,0 +1,471 @@
+use crate::iutil::{div_rem, forward_ref_op, from_u64, mul};
+use crate::num::{
+    BigNum, DefaultBigNumDigit, DynamicBigNum, SignedBigNum, SignedBigNumMut, SignedVecBigNum,
+    UnsignedNumDigit, VecBigNum,
+};
+use crate::ops::{SignMagnitudeEngine, SignedEngine, SimpleEngine, UnsignedEngine};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::*;
+
+type Engine = SignMagnitudeEngine<SimpleEngine>;
+
+/// Multiplies signed `lhs` by unsigned `rhs`.
+fn mul_signed<D>(lhs: &SignedVecBigNum<D>, rhs: &VecBigNum<D>) -> SignedVecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = SignedVecBigNum::new_zeroed();
+    Engine::mul_resize(&mut res, lhs, &SignedVecBigNum::from(rhs.clone()));
+    res
+}
+
+/// Rational number of arbitrary precision.
+///
+/// It's always kept in lowest terms with positive denominator, so each value has single representation
+/// and equality and hashing can compare components directly. Zero is `0/1`.
+#[derive(Debug, Clone)]
+pub struct BigRational<D = DefaultBigNumDigit> {
+    num: SignedVecBigNum<D>,
+    den: VecBigNum<D>,
+}
+
+impl<D> BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Creates rational `num / den` and reduces it to lowest terms.
+    ///
+    /// # Panic
+    /// Panics when `den` is zero.
+    pub fn new(num: SignedVecBigNum<D>, den: VecBigNum<D>) -> Self {
+        assert!(!den.is_zero(), "Denominator must not be zero");
+
+        let (mut num, minus) = num.into_parts();
+        let mut den = den;
+        if num.is_zero() {
+            return Self::zero();
+        }
+
+        let mut gcd = VecBigNum::new_zeroed();
+        SimpleEngine::gcd_resize(&mut gcd, &num, &den);
+        if !gcd.is_one() {
+            num = div_rem(&num, &gcd).0;
+            den = div_rem(&den, &gcd).0;
+        }
+        num.normalize();
+        den.normalize();
+
+        Self {
+            num: SignedVecBigNum::from_parts(num, minus),
+            den,
+        }
+    }
+
+    /// Creates rational, which has integer value.
+    pub fn from_integer(num: SignedVecBigNum<D>) -> Self {
+        Self::new(num, VecBigNum::from(vec![D::ONE]))
+    }
+
+    /// Returns zero.
+    pub fn zero() -> Self {
+        Self {
+            num: SignedVecBigNum::new_zeroed(),
+            den: VecBigNum::from(vec![D::ONE]),
+        }
+    }
+
+    /// Returns one.
+    pub fn one() -> Self {
+        Self::from_integer(SignedVecBigNum::from(VecBigNum::from(vec![D::ONE])))
+    }
+
+    /// Creates rational with exactly the same value as `v`.
+    /// Returns `None` for infinities and NaN.
+    pub fn from_f64(v: f64) -> Option<Self> {
+        if !v.is_finite() {
+            return None;
+        }
+
+        let bits = v.to_bits();
+        let minus = bits >> 63 == 1;
+        let biased_exp = ((bits >> 52) & 0x7ff) as i64;
+        let fraction = bits & ((1 << 52) - 1);
+
+        // value is mantissa * 2^exp, subnormals have no implicit bit
+        let (mantissa, exp) = if biased_exp == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), biased_exp - 1075)
+        };
+
+        let mut num = from_u64(mantissa);
+        let mut den = from_u64(1);
+        if exp >= 0 {
+            SimpleEngine::shift_left_resize(&mut num, exp as u64);
+        } else {
+            SimpleEngine::shift_left_resize(&mut den, -exp as u64);
+        }
+        Some(Self::new(SignedVecBigNum::from_parts(num, minus), den))
+    }
+
+    /// Returns numerator, which has sign of this number.
+    pub fn numer(&self) -> &SignedVecBigNum<D> {
+        &self.num
+    }
+
+    /// Returns denominator, which is always positive.
+    pub fn denom(&self) -> &VecBigNum<D> {
+        &self.den
+    }
+
+    /// Returns true if value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    /// Returns true if value is smaller than zero.
+    pub fn is_negative(&self) -> bool {
+        self.num.is_negative()
+    }
+
+    /// Returns true if value is integer, which is when denominator is one.
+    pub fn is_integer(&self) -> bool {
+        self.den.is_one()
+    }
+
+    /// Returns absolute value.
+    pub fn abs(&self) -> Self {
+        let mut res = self.clone();
+        res.num.set_minus(false);
+        res
+    }
+
+    /// Returns `1 / self`.
+    ///
+    /// # Panic
+    /// Panics when value is zero.
+    pub fn recip(&self) -> Self {
+        assert!(!self.is_zero(), "Division by zero");
+        Self {
+            num: SignedVecBigNum::from_parts(self.den.clone(), self.num.is_minus()),
+            den: self.num.magnitude().clone(),
+        }
+    }
+
+    /// Rounds absolute value, using `round_up` to decide if truncated quotient should be incremented.
+    /// `round_up` gets remainder of division.
+    fn round_with(&self, round_up: impl Fn(&VecBigNum<D>) -> bool) -> SignedVecBigNum<D> {
+        let (mut quot, rem) = div_rem(self.num.magnitude(), &self.den);
+        if !rem.is_zero() && round_up(&rem) {
+            SimpleEngine::add_resize(&mut quot, &from_u64(1));
+        }
+        let minus = self.is_negative() && !quot.is_zero();
+        SignedVecBigNum::from_parts(quot, minus)
+    }
+
+    /// Returns the largest integer not greater than this number.
+    pub fn floor(&self) -> SignedVecBigNum<D> {
+        self.round_with(|_| self.is_negative())
+    }
+
+    /// Returns the smallest integer not smaller than this number.
+    pub fn ceil(&self) -> SignedVecBigNum<D> {
+        self.round_with(|_| !self.is_negative())
+    }
+
+    /// Returns integer part of this number, so it rounds towards zero.
+    pub fn trunc(&self) -> SignedVecBigNum<D> {
+        self.round_with(|_| false)
+    }
+
+    /// Returns the nearest integer. Halfway values are rounded away from zero.
+    pub fn round(&self) -> SignedVecBigNum<D> {
+        self.round_with(|rem| {
+            let mut twice = rem.clone();
+            SimpleEngine::shift_left_resize(&mut twice, 1);
+            SimpleEngine::compare(&twice, &self.den) != Ordering::Less
+        })
+    }
+}
+
+impl<D> From<SignedVecBigNum<D>> for BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn from(num: SignedVecBigNum<D>) -> Self {
+        Self::from_integer(num)
+    }
+}
+
+impl<D> PartialEq for BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // both numbers are in lowest terms
+        self.num == other.num && self.den == other.den
+    }
+}
+
+impl<D> Eq for BigRational<D> where D: UnsignedNumDigit {}
+
+impl<D> PartialOrd for BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<D> Ord for BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // denominators are positive, so a/b < c/d is the same as a*d < c*b
+        mul_signed(&self.num, &other.den).cmp(&mul_signed(&other.num, &self.den))
+    }
+}
+
+impl<D> Hash for BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.num.hash(state);
+        self.den.hash(state);
+    }
+}
+
+impl<D> fmt::Display for BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Formats number as `n/d` in decimal. Integers are formatted with denominator `1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = format!("{}/{}", self.num.magnitude(), self.den);
+        f.pad_integral(!self.is_negative(), "", &text)
+    }
+}
+
+impl<D> Add for &BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = BigRational<D>;
+
+    fn add(self, rhs: Self) -> BigRational<D> {
+        let mut num = mul_signed(&self.num, &rhs.den);
+        Engine::add_resize(&mut num, &mul_signed(&rhs.num, &self.den));
+        BigRational::new(num, mul(&self.den, &rhs.den))
+    }
+}
+
+impl<D> Sub for &BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = BigRational<D>;
+
+    fn sub(self, rhs: Self) -> BigRational<D> {
+        let mut num = mul_signed(&self.num, &rhs.den);
+        Engine::sub_resize(&mut num, &mul_signed(&rhs.num, &self.den));
+        BigRational::new(num, mul(&self.den, &rhs.den))
+    }
+}
+
+impl<D> Mul for &BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = BigRational<D>;
+
+    fn mul(self, rhs: Self) -> BigRational<D> {
+        let mut num = SignedVecBigNum::new_zeroed();
+        Engine::mul_resize(&mut num, &self.num, &rhs.num);
+        BigRational::new(num, mul(&self.den, &rhs.den))
+    }
+}
+
+impl<D> Div for &BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = BigRational<D>;
+
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    fn div(self, rhs: Self) -> BigRational<D> {
+        Mul::mul(self, &rhs.recip())
+    }
+}
+
+impl<D> Neg for &BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = BigRational<D>;
+
+    fn neg(self) -> BigRational<D> {
+        let mut res = self.clone();
+        if !res.is_zero() {
+            res.num.set_minus(!res.num.is_minus());
+        }
+        res
+    }
+}
+
+impl<D> Neg for BigRational<D>
+where
+    D: UnsignedNumDigit,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        -&self
+    }
+}
+
+forward_ref_op!([D] BigRational<D>, Add, add, AddAssign, add_assign);
+forward_ref_op!([D] BigRational<D>, Sub, sub, SubAssign, sub_assign);
+forward_ref_op!([D] BigRational<D>, Mul, mul, MulAssign, mul_assign);
+forward_ref_op!([D] BigRational<D>, Div, div, DivAssign, div_assign);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::num::BigNumBits;
+
+    fn int(v: i64) -> SignedVecBigNum<u32> {
+        SignedVecBigNum::from_parts(from_u64(v.unsigned_abs()), v < 0)
+    }
+
+    fn ratio(num: i64, den: u64) -> BigRational<u32> {
+        BigRational::new(int(num), from_u64(den))
+    }
+
+    #[test]
+    fn test_lowest_terms() {
+        let r = ratio(-6, 8);
+        assert_eq!(r.numer(), &int(-3));
+        assert_eq!(r.denom(), &from_u64(4));
+        assert_eq!(ratio(0, 5), BigRational::zero());
+        assert_eq!(ratio(0, 5).denom(), &from_u64(1));
+        assert!(!ratio(0, 5).is_negative());
+        assert_eq!(ratio(10, 5), BigRational::from(int(2)));
+        assert!(ratio(10, 5).is_integer());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(ratio(1, 3) + ratio(1, 6), ratio(1, 2));
+        assert_eq!(ratio(1, 3) - ratio(1, 2), ratio(-1, 6));
+        assert_eq!(ratio(-2, 3) * ratio(9, 4), ratio(-3, 2));
+        assert_eq!(ratio(-2, 3) / ratio(-4, 9), ratio(3, 2));
+        assert_eq!(-ratio(5, 7), ratio(-5, 7));
+        assert_eq!(ratio(1, 3) - ratio(1, 3), BigRational::zero());
+        assert_eq!(ratio(-5, 7).abs(), ratio(5, 7));
+        assert_eq!(ratio(-5, 7).recip(), ratio(-7, 5));
+
+        // sum of 1/k for k in 1..=20
+        let mut sum = BigRational::zero();
+        for k in 1..=20 {
+            sum += ratio(1, k);
+        }
+        assert_eq!(sum.to_string(), "55835135/15519504");
+
+        let mut r = ratio(3, 4);
+        r *= &ratio(2, 3);
+        r -= ratio(1, 4);
+        r /= &ratio(1, 8);
+        assert_eq!(r, BigRational::from(int(2)));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut values = vec![
+            ratio(1, 2),
+            ratio(-1, 3),
+            ratio(2, 3),
+            ratio(0, 1),
+            ratio(-7, 2),
+            ratio(1, 3),
+        ];
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                ratio(-7, 2),
+                ratio(-1, 3),
+                ratio(0, 1),
+                ratio(1, 3),
+                ratio(1, 2),
+                ratio(2, 3)
+            ]
+        );
+        assert!(ratio(1, 3) > ratio(-1, 2));
+    }
+
+    #[test]
+    fn test_from_f64() {
+        assert_eq!(
+            BigRational::<u32>::from_f64(0.1).unwrap(),
+            ratio(3602879701896397, 36028797018963968)
+        );
+        assert_eq!(BigRational::from_f64(-2.5).unwrap(), ratio(-5, 2));
+        assert_eq!(
+            BigRational::<u32>::from_f64(1e20).unwrap().to_string(),
+            "100000000000000000000/1"
+        );
+        assert_eq!(
+            BigRational::<u32>::from_f64(0.0).unwrap(),
+            BigRational::zero()
+        );
+        assert_eq!(
+            BigRational::<u32>::from_f64(-0.0).unwrap(),
+            BigRational::zero()
+        );
+
+        let min = BigRational::<u32>::from_f64(f64::from_bits(1)).unwrap();
+        assert_eq!(min.numer(), &int(1));
+        assert_eq!(min.denom().bit_len(), 1075);
+        assert!(BigRational::<u32>::from_f64(f64::NAN).is_none());
+        assert!(BigRational::<u32>::from_f64(f64::INFINITY).is_none());
+    }
+
+    #[test]
+    fn test_rounding() {
+        let cases = [
+            // value, floor, ceil, trunc, round
+            (ratio(7, 2), 3, 4, 3, 4),
+            (ratio(-7, 2), -4, -3, -3, -4),
+            (ratio(5, 3), 1, 2, 1, 2),
+            (ratio(-5, 3), -2, -1, -1, -2),
+            (ratio(4, 3), 1, 2, 1, 1),
+            (ratio(-1, 3), -1, 0, 0, 0),
+            (ratio(6, 1), 6, 6, 6, 6),
+        ];
+        for (r, floor, ceil, trunc, round) in cases.iter() {
+            assert_eq!(r.floor(), int(*floor), "floor {}", r);
+            assert_eq!(r.ceil(), int(*ceil), "ceil {}", r);
+            assert_eq!(r.trunc(), int(*trunc), "trunc {}", r);
+            assert_eq!(r.round(), int(*round), "round {}", r);
+        }
+        assert!(!ratio(-1, 3).ceil().is_minus());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(ratio(-6, 8).to_string(), "-3/4");
+        assert_eq!(ratio(6, 3).to_string(), "2/1");
+        assert_eq!(BigRational::<u32>::zero().to_string(), "0/1");
+        assert_eq!(format!("{:>6}", ratio(1, 2)), "   1/2");
+        assert_eq!(int(-42).to_string(), "-42");
+        assert_eq!(BigRational::from(int(-42)).to_string(), "-42/1");
+    }
+}
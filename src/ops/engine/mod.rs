@@ -1,12 +1,35 @@
-use crate::num::{BigNum, BigNumMut, DynamicBigNum, UnsignedNumDigit};
+use crate::num::{
+    ArrayBigNum, BigNum, BigNumBits, BigNumBitsMut, BigNumMut, DynamicBigNum, SignedBigNum,
+    SignedBigNumMut, UnsignedNumDigit,
+};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
 
 mod simple;
 pub use self::simple::*;
 
+mod sign_magnitude;
+pub use self::sign_magnitude::*;
+
 /// Performs operations on unsigned numbers.
+///
+/// Digit and shift variants of operations have default implementations built on other operations,
+/// so engines only need to override them when they can do it faster.
 pub trait UnsignedEngine {
+    /// Compares values of `lhs` and `rhs`.
+    /// Numbers may have different amount of digits. Zero digits above the most significant one are ignored,
+    /// so `[1]` and `[1, 0]` are equal.
+    ///
+    /// # Return
+    /// Returns `Equal` variant when both lhs and rhs are zero sized.
+    fn compare<D>(lhs: &impl BigNum<Digit = D>, rhs: &impl BigNum<Digit = D>) -> Ordering
+    where
+        D: UnsignedNumDigit;
+
     /// Adds `rhs` to `res` and stores result into `res`.
     ///
+    /// `rhs` may have more digits than `res`. Its excess digits are not added, so they are expected to be zero.
+    ///
     /// # Return
     /// Returns true if result size does not fit res(AKA carry flag is set OR `rhs` has some digit set on position higher then res can have).
     fn add_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>) -> bool
@@ -15,31 +38,300 @@ pub trait UnsignedEngine {
 
     /// Subs `rhs` from `res` and stores result into `res`.
     ///
+    /// `rhs` may have more digits than `res`. Its excess digits are not subtracted, so they are expected to be zero.
+    ///
     /// # Return
-    /// Returns 1st true when result size does not fit res.
-    /// Returns 2nd true when borrowing from not existing bit occurred(AKA burrow flag is set).
+    /// Returns true when borrowing from not existing bit occurred(AKA burrow flag is set OR `rhs` has some digit set on position higher then res can have).
     fn sub_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>) -> bool
     where
         D: UnsignedNumDigit;
 
+    /// Adds `rhs` and `carry_in` to `res` and stores result into `res`.
+    /// Allows chaining additions of numbers split into multiple parts, from the least significant one.
+    ///
+    /// # Return
+    /// Returns carry out of the most significant digit of `res`.
+    /// Just like in `add_accumulate` it's also set when `rhs` has some digit set on position higher then res can have.
+    fn add_with_carry<D>(
+        res: &mut impl BigNumMut<Digit = D>,
+        rhs: &impl BigNum<Digit = D>,
+        carry_in: bool,
+    ) -> bool
+    where
+        D: UnsignedNumDigit;
+
+    /// Subs `rhs` and `borrow_in` from `res` and stores result into `res`.
+    /// Allows chaining subtractions of numbers split into multiple parts, from the least significant one.
+    ///
+    /// # Return
+    /// Returns borrow out of the most significant digit of `res`.
+    /// Just like in `sub_accumulate` it's also set when `rhs` has some digit set on position higher then res can have.
+    fn sub_with_borrow<D>(
+        res: &mut impl BigNumMut<Digit = D>,
+        rhs: &impl BigNum<Digit = D>,
+        borrow_in: bool,
+    ) -> bool
+    where
+        D: UnsignedNumDigit;
+
+    /// Adds digit `d` to `res` and stores result into `res`.
+    ///
+    /// # Return
+    /// Returns true if result size does not fit res(AKA carry flag is set).
+    fn add_digit<D>(res: &mut impl BigNumMut<Digit = D>, d: D) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        Self::add_accumulate(res, &ArrayBigNum::from([d]))
+    }
+
+    /// Subs digit `d` from `res` and stores result into `res`.
+    ///
+    /// # Return
+    /// Returns true when borrowing from not existing bit occurred(AKA burrow flag is set).
+    fn sub_digit<D>(res: &mut impl BigNumMut<Digit = D>, d: D) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        Self::sub_accumulate(res, &ArrayBigNum::from([d]))
+    }
+
+    /// Multiplies `res` by digit `d` and stores result into `res`.
+    ///
+    /// # Return
+    /// Returns digit, which does not fit `res`. It's zero when there is no overflow.
+    fn mul_digit<D>(res: &mut impl BigNumMut<Digit = D>, d: D) -> D
+    where
+        D: UnsignedNumDigit,
+    {
+        Self::mul_add_digit(res, d, D::ZERO)
+    }
+
+    /// Multiplies `res` by digit `m`, adds digit `a` to the product and stores result into `res`.
+    /// It's the inner loop of parsing numbers in any radix.
+    ///
+    /// # Return
+    /// Returns digit, which does not fit `res`. It's zero when there is no overflow.
+    fn mul_add_digit<D>(res: &mut impl BigNumMut<Digit = D>, m: D, a: D) -> D
+    where
+        D: UnsignedNumDigit,
+    {
+        if res.is_empty() {
+            return a;
+        }
+
+        // a is put into cleared res first, so product is accumulated on top of it
+        let lhs = res.clone();
+        for i in 0..res.len() {
+            res.set_digit(i, D::ZERO);
+        }
+        res.set_digit(0, a);
+        Self::mul_accumulate_carry(res, &lhs, &ArrayBigNum::from([m]))
+    }
+
+    /// Divides `res` by digit `d` and stores quotient into `res`.
+    /// It's the inner loop of printing numbers in any radix.
+    ///
+    /// # Return
+    /// Returns remainder of division.
+    ///
+    /// # Panic
+    /// Panics when `d` is zero.
+    fn div_rem_digit<D>(res: &mut impl BigNumMut<Digit = D>, d: D) -> D
+    where
+        D: UnsignedNumDigit,
+    {
+        assert!(d != D::ZERO, "Division by zero");
+
+        let lhs = res.clone();
+        let mut rem = ArrayBigNum::from([D::ZERO]);
+        Self::div_rem(res, &mut rem, &lhs, &ArrayBigNum::from([d]));
+        rem[0]
+    }
+
+    /// Returns remainder of division of `lhs` by digit `d`.
+    ///
+    /// # Panic
+    /// Panics when `d` is zero.
+    fn rem_digit<D>(lhs: &impl BigNum<Digit = D>, d: D) -> D
+    where
+        D: UnsignedNumDigit,
+    {
+        assert!(d != D::ZERO, "Division by zero");
+
+        // remainder is found digit by digit, high digit of each divided number is the previous remainder
+        let divisor = ArrayBigNum::from([d]);
+        let mut rem = ArrayBigNum::from([D::ZERO]);
+        for i in (0..lhs.len()).rev() {
+            let mut quot = ArrayBigNum::from([D::ZERO; 2]);
+            let num = ArrayBigNum::from([lhs[i], rem[0]]);
+            Self::div_rem(&mut quot, &mut rem, &num, &divisor);
+        }
+        rem[0]
+    }
+
+    /// Compares value of `lhs` with primitive integer `rhs`.
+    fn compare_u64<D>(lhs: &impl BigNum<Digit = D>, rhs: u64) -> Ordering
+    where
+        D: UnsignedNumDigit,
+    {
+        Self::compare_u128(lhs, rhs as u128)
+    }
+
+    /// Compares value of `lhs` with primitive integer `rhs`.
+    fn compare_u128<D>(lhs: &impl BigNum<Digit = D>, rhs: u128) -> Ordering
+    where
+        D: UnsignedNumDigit,
+    {
+        // digits have at least 8 bits, so 16 of them fit any u128
+        let mut num = ArrayBigNum::<D, 16>::new_zeroed();
+        for bit in 0..128 {
+            if (rhs >> bit) & 1 == 1 {
+                num.set_bit(bit);
+            }
+        }
+        Self::compare(lhs, &num)
+    }
+
     /// Shifts number to the left by `n` bits.
-    /// Does not resize number if result does not fit.
+    /// Does not resize number if result does not fit, bits shifted out of number are lost.
+    ///
+    /// Unlike for primitive integers `n` is not reduced modulo bit length of number,
+    /// so shifting by `n >= res.len() * NUM_BITS` clears the number.
     ///
     /// # Retrun
-    /// Returns true if result size does not fit `res`.
+    /// Returns true if `n` is greater or equal to bit length of `res`(AKA all bits were shifted out).
     fn shift_left_u32<D>(res: &mut impl BigNumMut<Digit = D>, n: u32) -> bool
     where
         D: UnsignedNumDigit;
 
-    /// Shifts number to the left by `n` bits.
-    /// Does not resize number if result does not fit.
+    /// Shifts number to the right by `n` bits.
+    /// Bits shifted out of number are lost.
+    ///
+    /// Unlike for primitive integers `n` is not reduced modulo bit length of number,
+    /// so shifting by `n >= res.len() * NUM_BITS` clears the number.
     ///
     /// # Retrun
-    /// Returns true if result size does not fit `res`.
+    /// Returns true if `n` is greater or equal to bit length of `res`(AKA all bits were shifted out).
     fn shift_right_u32<D>(res: &mut impl BigNumMut<Digit = D>, n: u32) -> bool
     where
         D: UnsignedNumDigit;
 
+    /// Shifts number to the left by `n` bits.
+    /// Works just like `shift_left_u32` but accepts bigger shift counts.
+    fn shift_left_usize<D>(res: &mut impl BigNumMut<Digit = D>, n: usize) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        Self::shift_left_u64(res, n as u64)
+    }
+
+    /// Shifts number to the left by `n` bits.
+    /// Works just like `shift_left_u32` but accepts bigger shift counts.
+    fn shift_left_u64<D>(res: &mut impl BigNumMut<Digit = D>, n: u64) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        let total_bits = (res.len() as u64).saturating_mul(D::NUM_BITS as u64);
+        let overflow = n >= total_bits;
+        // shifting by bit length already clears the number, count is split into parts fitting u32
+        let mut n = n.min(total_bits);
+        while n > u32::MAX as u64 {
+            Self::shift_left_u32(res, u32::MAX);
+            n -= u32::MAX as u64;
+        }
+        Self::shift_left_u32(res, n as u32);
+        overflow
+    }
+
+    /// Shifts number to the right by `n` bits.
+    /// Works just like `shift_right_u32` but accepts bigger shift counts.
+    fn shift_right_usize<D>(res: &mut impl BigNumMut<Digit = D>, n: usize) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        Self::shift_right_u64(res, n as u64)
+    }
+
+    /// Shifts number to the right by `n` bits.
+    /// Works just like `shift_right_u32` but accepts bigger shift counts.
+    fn shift_right_u64<D>(res: &mut impl BigNumMut<Digit = D>, n: u64) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        let total_bits = (res.len() as u64).saturating_mul(D::NUM_BITS as u64);
+        let overflow = n >= total_bits;
+        // shifting by bit length already clears the number, count is split into parts fitting u32
+        let mut n = n.min(total_bits);
+        while n > u32::MAX as u64 {
+            Self::shift_right_u32(res, u32::MAX);
+            n -= u32::MAX as u64;
+        }
+        Self::shift_right_u32(res, n as u32);
+        overflow
+    }
+
+    /// Shifts number to the left by `n` bits and resizes `res` in order to make it fit all bits of result.
+    ///
+    /// # Panic
+    /// Panics when result is so big, that its digits count does not fit `usize`.
+    fn shift_left_resize<D, M>(res: &mut M, n: u64)
+    where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        let bit_len = res.bit_len() as u64;
+        if bit_len == 0 {
+            // zero stays zero, no matter how far it's shifted
+            return;
+        }
+
+        let size = bit_len
+            .checked_add(n)
+            .and_then(|bits| usize::try_from((bits - 1) / D::NUM_BITS as u64 + 1).ok())
+            .expect("Shifted number does not fit memory");
+        if res.len() < size {
+            res.resize(size);
+        }
+
+        let overflow = Self::shift_left_u64(res, n);
+        debug_assert!(!overflow);
+    }
+
+    /// Rotates number to the left by `n` bits, so bits shifted out of the most significant digit
+    /// are put into the least significant one.
+    /// Number is treated as fixed width one with `res.len() * NUM_BITS` bits, so `n` is reduced modulo that value.
+    fn rotate_left<D>(res: &mut impl BigNumMut<Digit = D>, n: usize)
+    where
+        D: UnsignedNumDigit,
+    {
+        let total_bits = res.len() * D::NUM_BITS as usize;
+        if total_bits == 0 {
+            return;
+        }
+        let n = n % total_bits;
+
+        // bits of both parts don't overlap, so adding them is the same as or-ing them
+        let mut high = res.clone();
+        Self::shift_right_usize(&mut high, total_bits - n);
+        Self::shift_left_usize(res, n);
+        Self::add_accumulate(res, &high);
+    }
+
+    /// Rotates number to the right by `n` bits, so bits shifted out of the least significant digit
+    /// are put into the most significant one.
+    /// Number is treated as fixed width one with `res.len() * NUM_BITS` bits, so `n` is reduced modulo that value.
+    fn rotate_right<D>(res: &mut impl BigNumMut<Digit = D>, n: usize)
+    where
+        D: UnsignedNumDigit,
+    {
+        let total_bits = res.len() * D::NUM_BITS as usize;
+        if total_bits == 0 {
+            return;
+        }
+        Self::rotate_left(res, total_bits - n % total_bits);
+    }
+
     /// Multiplies `lhs` and `rhs` and adds result of multiplication to res.
     ///
     /// # Note
@@ -57,6 +349,25 @@ pub trait UnsignedEngine {
     where
         D: UnsignedNumDigit;
 
+    /// Multiplies `lhs` and `rhs` and adds result of multiplication to `res`.
+    /// Unlike `mul_accumulate` it does not lose part of result, which does not fit `res`.
+    ///
+    /// # Return
+    /// Returns overflowing digit, which is value of result shifted right by `res.len()` digits.
+    /// It's always zero when result fits `res`.
+    ///
+    /// # Panic
+    /// Panics when overflowing part of result does not fit single digit.
+    /// It never happens when `res` has at least `lhs.significant_len() + rhs.significant_len()` digits
+    /// or when `rhs` has single significant digit and `res` has at least `lhs.significant_len()` digits.
+    fn mul_accumulate_carry<D>(
+        res: &mut impl BigNumMut<Digit = D>,
+        lhs: &impl BigNum<Digit = D>,
+        rhs: &impl BigNum<Digit = D>,
+    ) -> D
+    where
+        D: UnsignedNumDigit;
+
     /// Multiplies `lhs` and `rhs` and adds result of multiplication to `res`.
     /// Resizes `res` to fit result.
     ///
@@ -81,4 +392,545 @@ pub trait UnsignedEngine {
     where
         M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
         D: UnsignedNumDigit;
+
+    /// Divides `lhs` by `rhs`, stores quotient in `quot` and remainder in `rem`.
+    /// Previous values of `quot` and `rem` are overwritten.
+    ///
+    /// # Panic
+    /// Panics when `rhs` is zero or when `rem` can't store all values smaller than `rhs`.
+    ///
+    /// # Return
+    /// Returns `true` when quotient does not fit `quot`.
+    /// Remainder is correct even in that case.
+    fn div_rem<D>(
+        quot: &mut impl BigNumMut<Digit = D>,
+        rem: &mut impl BigNumMut<Digit = D>,
+        lhs: &impl BigNum<Digit = D>,
+        rhs: &impl BigNum<Digit = D>,
+    ) -> bool
+    where
+        D: UnsignedNumDigit;
+
+    /// Divides `lhs` by `rhs`, stores quotient in `quot` and remainder in `rem`.
+    /// Resizes `quot` and `rem` in order to make them fit results.
+    ///
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    fn div_rem_resize<D, M>(
+        quot: &mut M,
+        rem: &mut M,
+        lhs: &impl BigNum<Digit = D>,
+        rhs: &impl BigNum<Digit = D>,
+    ) where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit;
+
+    /// Computes `base` raised to `exp` modulo `modulus` and stores result into `res`.
+    /// Resizes `res` in order to make it fit result.
+    ///
+    /// # Note
+    /// It needs temporary numbers, which are allocated as `M`.
+    ///
+    /// # Panic
+    /// Panics when `modulus` is zero.
+    fn pow_mod_resize<D, M>(
+        res: &mut M,
+        base: &impl BigNum<Digit = D>,
+        exp: &impl BigNum<Digit = D>,
+        modulus: &impl BigNum<Digit = D>,
+    ) where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit;
+
+    /// Computes greatest common divisor of `lhs` and `rhs` and stores it into `res`.
+    /// Resizes `res` in order to make it fit result.
+    /// Greatest common divisor of zero and zero is zero.
+    fn gcd_resize<D, M>(res: &mut M, lhs: &impl BigNum<Digit = D>, rhs: &impl BigNum<Digit = D>)
+    where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit;
+
+    /// Computes multiplicative inverse of `value` modulo `modulus` and stores it into `res`.
+    /// Resizes `res` in order to make it fit result.
+    ///
+    /// # Return
+    /// Returns false when `value` has no inverse, which happens when it's not coprime with `modulus`.
+    /// In that case `res` is not modified.
+    ///
+    /// # Panic
+    /// Panics when `modulus` is zero.
+    fn inv_mod_resize<D, M>(
+        res: &mut M,
+        value: &impl BigNum<Digit = D>,
+        modulus: &impl BigNum<Digit = D>,
+    ) -> bool
+    where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit;
+
+    /// Computes bitwise and of `res` and `rhs` and stores result into `res`.
+    /// Operands may have different lengths. Shorter one is treated as if it was extended with zero digits,
+    /// so digits of `res` above `rhs.len()` are cleared.
+    fn and_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>)
+    where
+        D: UnsignedNumDigit;
+
+    /// Computes bitwise and of `res` and negated `rhs` (AKA clears in `res` bits set in `rhs`) and stores result into `res`.
+    /// Operands may have different lengths. Shorter one is treated as if it was extended with zero digits,
+    /// so digits of `res` above `rhs.len()` are left untouched.
+    fn and_not_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>)
+    where
+        D: UnsignedNumDigit;
+
+    /// Computes bitwise or of `res` and `rhs` and stores result into `res`.
+    /// Operands may have different lengths. Shorter one is treated as if it was extended with zero digits.
+    ///
+    /// # Return
+    /// Returns true if result size does not fit `res`(AKA `rhs` has some bits set on positions `res` does not have).
+    fn or_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>) -> bool
+    where
+        D: UnsignedNumDigit;
+
+    /// Computes bitwise xor of `res` and `rhs` and stores result into `res`.
+    /// Operands may have different lengths. Shorter one is treated as if it was extended with zero digits.
+    ///
+    /// # Return
+    /// Returns true if result size does not fit `res`(AKA `rhs` has some bits set on positions `res` does not have).
+    fn xor_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>) -> bool
+    where
+        D: UnsignedNumDigit;
+
+    /// Negates all bits of `res`.
+    /// Number is treated as fixed width one, so only `res.len()` digits are negated.
+    fn not_in_place<D>(res: &mut impl BigNumMut<Digit = D>)
+    where
+        D: UnsignedNumDigit;
+
+    /// Computes bitwise or of `res` and `rhs` and resizes `res` in order to make it fit result.
+    ///
+    /// # Note
+    /// There are no resizing variants of and and and-not, since their results always fit `res`.
+    fn or_resize<D, M>(res: &mut M, rhs: &impl BigNum<Digit = D>)
+    where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit;
+
+    /// Computes bitwise xor of `res` and `rhs` and resizes `res` in order to make it fit result.
+    fn xor_resize<D, M>(res: &mut M, rhs: &impl BigNum<Digit = D>)
+    where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit;
+}
+
+/// Performs operations on signed numbers.
+///
+/// Results never have minus flag set when their value is zero.
+pub trait SignedEngine {
+    /// Adds `rhs` to `res` and resizes `res` in order to make it fit result of addition.
+    fn add_resize<D, M>(res: &mut M, rhs: &impl SignedBigNum<Digit = D>)
+    where
+        M: DynamicBigNum<Digit = D> + SignedBigNumMut<Digit = D>,
+        D: UnsignedNumDigit;
+
+    /// Subs `rhs` from `res` and resizes `res` in order to make it fit result of subtraction.
+    fn sub_resize<D, M>(res: &mut M, rhs: &impl SignedBigNum<Digit = D>)
+    where
+        M: DynamicBigNum<Digit = D> + SignedBigNumMut<Digit = D>,
+        D: UnsignedNumDigit;
+
+    /// Multiplies `lhs` and `rhs` and adds result of multiplication to `res`.
+    /// Resizes `res` to fit result.
+    fn mul_resize<D, M>(
+        res: &mut M,
+        lhs: &impl SignedBigNum<Digit = D>,
+        rhs: &impl SignedBigNum<Digit = D>,
+    ) where
+        M: DynamicBigNum<Digit = D> + SignedBigNumMut<Digit = D>,
+        D: UnsignedNumDigit;
+
+    /// Divides `lhs` by `rhs`, stores quotient in `quot` and remainder in `rem`.
+    /// Resizes `quot` and `rem` in order to make them fit results.
+    ///
+    /// Division truncates towards zero and remainder has sign of `lhs`, just like for primitive integers.
+    ///
+    /// # Panic
+    /// Panics when `rhs` is zero.
+    fn div_rem_resize<D, M>(
+        quot: &mut M,
+        rem: &mut M,
+        lhs: &impl SignedBigNum<Digit = D>,
+        rhs: &impl SignedBigNum<Digit = D>,
+    ) where
+        M: DynamicBigNum<Digit = D> + SignedBigNumMut<Digit = D>,
+        D: UnsignedNumDigit;
+
+    /// Shifts absolute value of number to the left by `n` bits.
+    /// Does not resize number if result does not fit.
+    ///
+    /// # Return
+    /// Returns true if result size does not fit `res`.
+    fn shift_left_u32<D>(res: &mut impl SignedBigNumMut<Digit = D>, n: u32) -> bool
+    where
+        D: UnsignedNumDigit;
+
+    /// Shifts number to the right by `n` bits.
+    ///
+    /// Just like for primitive integers result is rounded towards negative infinity,
+    /// so shifting any negative number far enough gives `-1`.
+    fn shift_right_u32<D>(res: &mut impl SignedBigNumMut<Digit = D>, n: u32)
+    where
+        D: UnsignedNumDigit;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::num::VecBigNum;
+
+    /// Engine, which implements only required operations, so others use their default implementations.
+    struct RequiredEngine;
+
+    impl UnsignedEngine for RequiredEngine {
+        fn compare<D>(lhs: &impl BigNum<Digit = D>, rhs: &impl BigNum<Digit = D>) -> Ordering
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::compare(lhs, rhs)
+        }
+
+        fn add_accumulate<D>(
+            res: &mut impl BigNumMut<Digit = D>,
+            rhs: &impl BigNum<Digit = D>,
+        ) -> bool
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::add_accumulate(res, rhs)
+        }
+
+        fn sub_accumulate<D>(
+            res: &mut impl BigNumMut<Digit = D>,
+            rhs: &impl BigNum<Digit = D>,
+        ) -> bool
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::sub_accumulate(res, rhs)
+        }
+
+        fn add_with_carry<D>(
+            res: &mut impl BigNumMut<Digit = D>,
+            rhs: &impl BigNum<Digit = D>,
+            carry_in: bool,
+        ) -> bool
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::add_with_carry(res, rhs, carry_in)
+        }
+
+        fn sub_with_borrow<D>(
+            res: &mut impl BigNumMut<Digit = D>,
+            rhs: &impl BigNum<Digit = D>,
+            borrow_in: bool,
+        ) -> bool
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::sub_with_borrow(res, rhs, borrow_in)
+        }
+
+        fn shift_left_u32<D>(res: &mut impl BigNumMut<Digit = D>, n: u32) -> bool
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::shift_left_u32(res, n)
+        }
+
+        fn shift_right_u32<D>(res: &mut impl BigNumMut<Digit = D>, n: u32) -> bool
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::shift_right_u32(res, n)
+        }
+
+        fn mul_accumulate<D>(
+            res: &mut impl BigNumMut<Digit = D>,
+            lhs: &impl BigNum<Digit = D>,
+            rhs: &impl BigNum<Digit = D>,
+        ) -> bool
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::mul_accumulate(res, lhs, rhs)
+        }
+
+        fn mul_accumulate_carry<D>(
+            res: &mut impl BigNumMut<Digit = D>,
+            lhs: &impl BigNum<Digit = D>,
+            rhs: &impl BigNum<Digit = D>,
+        ) -> D
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::mul_accumulate_carry(res, lhs, rhs)
+        }
+
+        fn mul_resize<D, M>(res: &mut M, lhs: &impl BigNum<Digit = D>, rhs: &impl BigNum<Digit = D>)
+        where
+            M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::mul_resize(res, lhs, rhs)
+        }
+
+        fn sub_resize<D, M>(res: &mut M, rhs: &impl BigNum<Digit = D>) -> bool
+        where
+            M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::sub_resize(res, rhs)
+        }
+
+        fn add_resize<D, M>(res: &mut M, rhs: &impl BigNum<Digit = D>)
+        where
+            M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::add_resize(res, rhs)
+        }
+
+        fn div_rem<D>(
+            quot: &mut impl BigNumMut<Digit = D>,
+            rem: &mut impl BigNumMut<Digit = D>,
+            lhs: &impl BigNum<Digit = D>,
+            rhs: &impl BigNum<Digit = D>,
+        ) -> bool
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::div_rem(quot, rem, lhs, rhs)
+        }
+
+        fn div_rem_resize<D, M>(
+            quot: &mut M,
+            rem: &mut M,
+            lhs: &impl BigNum<Digit = D>,
+            rhs: &impl BigNum<Digit = D>,
+        ) where
+            M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::div_rem_resize(quot, rem, lhs, rhs)
+        }
+
+        fn pow_mod_resize<D, M>(
+            res: &mut M,
+            base: &impl BigNum<Digit = D>,
+            exp: &impl BigNum<Digit = D>,
+            modulus: &impl BigNum<Digit = D>,
+        ) where
+            M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::pow_mod_resize(res, base, exp, modulus)
+        }
+
+        fn gcd_resize<D, M>(res: &mut M, lhs: &impl BigNum<Digit = D>, rhs: &impl BigNum<Digit = D>)
+        where
+            M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::gcd_resize(res, lhs, rhs)
+        }
+
+        fn inv_mod_resize<D, M>(
+            res: &mut M,
+            value: &impl BigNum<Digit = D>,
+            modulus: &impl BigNum<Digit = D>,
+        ) -> bool
+        where
+            M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::inv_mod_resize(res, value, modulus)
+        }
+
+        fn and_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>)
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::and_accumulate(res, rhs)
+        }
+
+        fn and_not_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>)
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::and_not_accumulate(res, rhs)
+        }
+
+        fn or_accumulate<D>(
+            res: &mut impl BigNumMut<Digit = D>,
+            rhs: &impl BigNum<Digit = D>,
+        ) -> bool
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::or_accumulate(res, rhs)
+        }
+
+        fn xor_accumulate<D>(
+            res: &mut impl BigNumMut<Digit = D>,
+            rhs: &impl BigNum<Digit = D>,
+        ) -> bool
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::xor_accumulate(res, rhs)
+        }
+
+        fn not_in_place<D>(res: &mut impl BigNumMut<Digit = D>)
+        where
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::not_in_place(res)
+        }
+
+        fn or_resize<D, M>(res: &mut M, rhs: &impl BigNum<Digit = D>)
+        where
+            M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::or_resize(res, rhs)
+        }
+
+        fn xor_resize<D, M>(res: &mut M, rhs: &impl BigNum<Digit = D>)
+        where
+            M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+            D: UnsignedNumDigit,
+        {
+            SimpleEngine::xor_resize(res, rhs)
+        }
+    }
+    const VALUES: &[u32] = &[0, 1, 0xff, 0x100, 0xfffe, 0x12_3456, 0xff_ffff];
+    const DIGITS: &[u8] = &[0, 1, 3, 0x10, 0xfe, 0xff];
+
+    /// Returns numbers with given value and different amounts of digits, including no digits for zero.
+    fn numbers(v: u32) -> Vec<VecBigNum<u8>> {
+        (0..5)
+            .filter(|&len| len >= 3 || v >> (8 * len) == 0)
+            .map(|len| VecBigNum::from(Vec::from(&v.to_le_bytes()[..len])))
+            .collect()
+    }
+
+    #[test]
+    fn test_default_digit_ops() {
+        for &v in VALUES {
+            for num in numbers(v) {
+                for &d in DIGITS {
+                    let (mut res, mut expected) = (num.clone(), num.clone());
+                    let overflow = RequiredEngine::add_digit(&mut res, d);
+                    assert_eq!(overflow, SimpleEngine::add_digit(&mut expected, d));
+                    assert_eq!(res.into_inner(), expected.into_inner());
+
+                    let (mut res, mut expected) = (num.clone(), num.clone());
+                    let borrow = RequiredEngine::sub_digit(&mut res, d);
+                    assert_eq!(borrow, SimpleEngine::sub_digit(&mut expected, d));
+                    assert_eq!(res.into_inner(), expected.into_inner());
+
+                    let (mut res, mut expected) = (num.clone(), num.clone());
+                    let carry = RequiredEngine::mul_digit(&mut res, d);
+                    assert_eq!(carry, SimpleEngine::mul_digit(&mut expected, d));
+                    assert_eq!(res.into_inner(), expected.into_inner());
+
+                    for &a in DIGITS {
+                        let (mut res, mut expected) = (num.clone(), num.clone());
+                        let carry = RequiredEngine::mul_add_digit(&mut res, d, a);
+                        assert_eq!(carry, SimpleEngine::mul_add_digit(&mut expected, d, a));
+                        assert_eq!(res.into_inner(), expected.into_inner());
+                    }
+
+                    if d != 0 {
+                        let (mut res, mut expected) = (num.clone(), num.clone());
+                        let rem = RequiredEngine::div_rem_digit(&mut res, d);
+                        assert_eq!(rem, SimpleEngine::div_rem_digit(&mut expected, d));
+                        assert_eq!(res.into_inner(), expected.into_inner());
+                        assert_eq!(
+                            RequiredEngine::rem_digit(&num, d),
+                            SimpleEngine::rem_digit(&num, d)
+                        );
+                    }
+                }
+
+                for &rhs in VALUES {
+                    assert_eq!(RequiredEngine::compare_u64(&num, rhs as u64), v.cmp(&rhs));
+                    assert_eq!(
+                        RequiredEngine::compare_u128(&num, rhs as u128 | 1 << 100),
+                        Ordering::Less
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_shifts() {
+        for &v in VALUES {
+            for num in numbers(v) {
+                for &n in &[
+                    0u64,
+                    1,
+                    7,
+                    8,
+                    9,
+                    17,
+                    24,
+                    25,
+                    40,
+                    u32::MAX as u64 + 3,
+                    u64::MAX,
+                ] {
+                    let (mut res, mut expected) = (num.clone(), num.clone());
+                    let overflow = RequiredEngine::shift_left_u64(&mut res, n);
+                    assert_eq!(overflow, SimpleEngine::shift_left_u64(&mut expected, n));
+                    assert_eq!(res.into_inner(), expected.into_inner());
+
+                    let (mut res, mut expected) = (num.clone(), num.clone());
+                    let overflow = RequiredEngine::shift_right_u64(&mut res, n);
+                    assert_eq!(overflow, SimpleEngine::shift_right_u64(&mut expected, n));
+                    assert_eq!(res.into_inner(), expected.into_inner());
+
+                    let (mut res, mut expected) = (num.clone(), num.clone());
+                    let overflow = RequiredEngine::shift_left_usize(&mut res, n as usize);
+                    assert_eq!(overflow, SimpleEngine::shift_left_u64(&mut expected, n));
+                    assert_eq!(res.into_inner(), expected.into_inner());
+
+                    let (mut res, mut expected) = (num.clone(), num.clone());
+                    let overflow = RequiredEngine::shift_right_usize(&mut res, n as usize);
+                    assert_eq!(overflow, SimpleEngine::shift_right_u64(&mut expected, n));
+                    assert_eq!(res.into_inner(), expected.into_inner());
+
+                    let (mut res, mut expected) = (num.clone(), num.clone());
+                    RequiredEngine::rotate_left(&mut res, n as usize);
+                    SimpleEngine::rotate_left(&mut expected, n as usize);
+                    assert_eq!(res.into_inner(), expected.into_inner());
+
+                    let (mut res, mut expected) = (num.clone(), num.clone());
+                    RequiredEngine::rotate_right(&mut res, n as usize);
+                    SimpleEngine::rotate_right(&mut expected, n as usize);
+                    assert_eq!(res.into_inner(), expected.into_inner());
+
+                    if n < 100 {
+                        let mut res = num.clone();
+                        RequiredEngine::shift_left_resize(&mut res, n);
+                        let mut expected = num.clone();
+                        expected.resize(expected.len() + n as usize / 8 + 1);
+                        SimpleEngine::shift_left_u64(&mut expected, n);
+                        assert_eq!(res, expected);
+                    }
+                }
+            }
+        }
+    }
 }
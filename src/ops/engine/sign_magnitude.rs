@@ -0,0 +1,270 @@
+use crate::num::{
+    BigNumBits, BigNumMut, DynamicBigNum, SignedBigNum, SignedBigNumMut, UnsignedNumDigit,
+};
+use crate::ops::{SignedEngine, SimpleEngine, UnsignedEngine};
+use core::marker::PhantomData;
+
+/// Replaces absolute value of `res` with `2^(res.len() * NUM_BITS) - |res|`.
+/// Used to fix result of subtraction, which borrowed from not existing bit.
+fn negate_magnitude<D>(res: &mut impl BigNumMut<Digit = D>)
+where
+    D: UnsignedNumDigit,
+{
+    let mut carry = true;
+    for i in 0..res.len() {
+        let (n, overflow) = (!res[i]).overflowing_add(if carry { D::ONE } else { D::ZERO });
+        carry = overflow;
+        res[i] = n;
+    }
+}
+
+/// Clears minus flag if value of `res` is zero.
+fn normalize_zero<D>(res: &mut impl SignedBigNumMut<Digit = D>)
+where
+    D: UnsignedNumDigit,
+{
+    if res.is_zero() {
+        res.set_minus(false);
+    }
+}
+
+/// Adds or subtracts absolute values of `res` and `rhs`, depending on `negate_rhs` and signs of numbers.
+fn add_signed_resize<E, D, M>(res: &mut M, rhs: &impl SignedBigNum<Digit = D>, negate_rhs: bool)
+where
+    E: UnsignedEngine,
+    M: DynamicBigNum<Digit = D> + SignedBigNumMut<Digit = D>,
+    D: UnsignedNumDigit,
+{
+    if res.is_minus() == (rhs.is_minus() != negate_rhs) {
+        E::add_resize(res, rhs);
+    } else {
+        // |res| - |rhs|; if it borrows then |rhs| > |res| and result has sign of rhs
+        let borrow = E::sub_resize(res, rhs);
+        if borrow {
+            negate_magnitude(res);
+            res.set_minus(!res.is_minus());
+        }
+    }
+    normalize_zero(res);
+}
+
+/// Sign-magnitude engine performs signed operations on absolute values of numbers
+/// using unsigned engine `E` and handles signs separately.
+pub struct SignMagnitudeEngine<E = SimpleEngine> {
+    _engine: PhantomData<E>,
+}
+
+impl<E> SignedEngine for SignMagnitudeEngine<E>
+where
+    E: UnsignedEngine,
+{
+    fn add_resize<D, M>(res: &mut M, rhs: &impl SignedBigNum<Digit = D>)
+    where
+        M: DynamicBigNum<Digit = D> + SignedBigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        add_signed_resize::<E, _, _>(res, rhs, false)
+    }
+
+    fn sub_resize<D, M>(res: &mut M, rhs: &impl SignedBigNum<Digit = D>)
+    where
+        M: DynamicBigNum<Digit = D> + SignedBigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        add_signed_resize::<E, _, _>(res, rhs, true)
+    }
+
+    fn mul_resize<D, M>(
+        res: &mut M,
+        lhs: &impl SignedBigNum<Digit = D>,
+        rhs: &impl SignedBigNum<Digit = D>,
+    ) where
+        M: DynamicBigNum<Digit = D> + SignedBigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        let mut product = M::new_zeroed();
+        E::mul_resize(&mut product, lhs, rhs);
+        product.set_minus(lhs.is_minus() != rhs.is_minus());
+
+        Self::add_resize(res, &product);
+    }
+
+    fn div_rem_resize<D, M>(
+        quot: &mut M,
+        rem: &mut M,
+        lhs: &impl SignedBigNum<Digit = D>,
+        rhs: &impl SignedBigNum<Digit = D>,
+    ) where
+        M: DynamicBigNum<Digit = D> + SignedBigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        E::div_rem_resize(quot, rem, lhs, rhs);
+
+        quot.set_minus(lhs.is_minus() != rhs.is_minus());
+        rem.set_minus(lhs.is_minus());
+
+        normalize_zero(quot);
+        normalize_zero(rem);
+    }
+
+    fn shift_left_u32<D>(res: &mut impl SignedBigNumMut<Digit = D>, n: u32) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        // Result fits only if all bits of absolute value stay in the number
+        let total_bits = res.len() as u64 * D::NUM_BITS as u64;
+        let overflow = res.bit_len() as u64 + n as u64 > total_bits && !res.is_zero();
+
+        E::shift_left_u32(res, n);
+        normalize_zero(res);
+        overflow
+    }
+
+    fn shift_right_u32<D>(res: &mut impl SignedBigNumMut<Digit = D>, n: u32)
+    where
+        D: UnsignedNumDigit,
+    {
+        let total_bits = res.len() as u64 * D::NUM_BITS as u64;
+
+        // 1. Check if any set bit is going to be shifted out
+        let lost = if n as u64 >= total_bits {
+            (0..res.len()).any(|i| res[i] != D::ZERO)
+        } else {
+            let digits = (n / D::NUM_BITS) as usize;
+            let bits = n % D::NUM_BITS;
+            (0..digits).any(|i| res[i] != D::ZERO)
+                || (bits > 0 && (res[digits] << (D::NUM_BITS - bits)) != D::ZERO)
+        };
+
+        // 2. Shift absolute value
+        E::shift_right_u32(res, n);
+
+        // 3. Round towards negative infinity
+        // after shift by at least one bit highest bit is clear, so adding one can't overflow
+        if res.is_minus() && lost {
+            let mut i = 0;
+            loop {
+                let (d, overflow) = res[i].overflowing_add(D::ONE);
+                res[i] = d;
+                if !overflow {
+                    break;
+                }
+                i += 1;
+            }
+        }
+        normalize_zero(res);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::num::{BigNum, SignedVecBigNum, VecBigNum};
+
+    type Engine = SignMagnitudeEngine<SimpleEngine>;
+
+    fn from_i64(v: i64) -> SignedVecBigNum<u8> {
+        let num = VecBigNum::from(Vec::from(&v.unsigned_abs().to_le_bytes()[..]));
+        SignedVecBigNum::from_parts(num, v < 0)
+    }
+
+    fn to_i64(v: &SignedVecBigNum<u8>) -> i64 {
+        let mut bytes = [0u8; 8];
+        for i in 0..v.len() {
+            if i < bytes.len() {
+                bytes[i] = v[i];
+            } else {
+                assert_eq!(v[i], 0);
+            }
+        }
+        let abs = u64::from_le_bytes(bytes) as i64;
+        if v.is_minus() {
+            assert_ne!(abs, 0, "negative zero");
+            -abs
+        } else {
+            abs
+        }
+    }
+
+    const VALUES: &[i64] = &[
+        0, 1, -1, 2, -2, 7, -7, 255, -255, 256, -256, 1000, -1000, 65535, -65536, 123456789,
+        -987654321,
+    ];
+
+    #[test]
+    fn test_eq_uses_sign() {
+        assert_ne!(from_i64(1), from_i64(-1));
+        assert_ne!(from_i64(-255), from_i64(255));
+        assert!(from_i64(-1) < from_i64(1));
+        assert_eq!(from_i64(-1).magnitude(), from_i64(1).magnitude());
+        assert_eq!(
+            SignedVecBigNum::from_parts(VecBigNum::<u8>::new_zeroed(), true),
+            from_i64(0)
+        );
+    }
+
+    #[test]
+    fn test_add_sub() {
+        for &a in VALUES {
+            for &b in VALUES {
+                let mut res = from_i64(a);
+                Engine::add_resize(&mut res, &from_i64(b));
+                assert_eq!(to_i64(&res), a + b, "{} + {}", a, b);
+
+                let mut res = from_i64(a);
+                Engine::sub_resize(&mut res, &from_i64(b));
+                assert_eq!(to_i64(&res), a - b, "{} - {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_div() {
+        for &a in VALUES {
+            for &b in VALUES {
+                let mut res = from_i64(7);
+                Engine::mul_resize(&mut res, &from_i64(a), &from_i64(b));
+                assert_eq!(to_i64(&res), 7 + a * b, "7 + {} * {}", a, b);
+
+                if b != 0 {
+                    let mut quot = SignedVecBigNum::new_zeroed();
+                    let mut rem = SignedVecBigNum::new_zeroed();
+                    Engine::div_rem_resize(&mut quot, &mut rem, &from_i64(a), &from_i64(b));
+                    assert_eq!(to_i64(&quot), a / b, "{} / {}", a, b);
+                    assert_eq!(to_i64(&rem), a % b, "{} % {}", a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shift() {
+        for &a in VALUES {
+            for n in 0..70 {
+                let mut res = from_i64(a);
+                Engine::shift_right_u32(&mut res, n);
+                assert_eq!(to_i64(&res), a >> n.min(63), "{} >> {}", a, n);
+            }
+            for n in 0..8 {
+                let mut res = from_i64(a);
+                res.resize(16);
+                assert!(!Engine::shift_left_u32(&mut res, n));
+                assert_eq!(to_i64(&res), a << n, "{} << {}", a, n);
+            }
+        }
+
+        // Shifts by less than width, which push set bits out of the number
+        let mut res = from_i64(-0x40);
+        res.resize(1);
+        assert!(!Engine::shift_left_u32(&mut res, 1));
+        assert!(Engine::shift_left_u32(&mut res, 1));
+        let mut res = from_i64(0x1ff);
+        res.resize(2);
+        assert!(!Engine::shift_left_u32(&mut res, 7));
+        let mut res = from_i64(-0x1ff);
+        res.resize(2);
+        assert!(Engine::shift_left_u32(&mut res, 8));
+        let mut res = from_i64(0);
+        assert!(!Engine::shift_left_u32(&mut res, 100));
+    }
+}
@@ -1,39 +1,34 @@
-use crate::num::{BigNum, BigNumMut, DynamicBigNum, UnsignedNumDigit};
+use crate::num::{BigNum, BigNumBits, BigNumMut, DynamicBigNum, UnsignedNumDigit};
 use crate::ops::UnsignedEngine;
-
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+
+/// Multiplies `lhs` by digit `d` and adds result shifted by `offset` digits to `res`,
+/// which is treated as if it had one more digit `top` above its most significant one.
+///
+/// # Return
+/// Returns true when some nonzero digits of product could not be stored in `res` and `top`.
 fn mul_accumulate_digit<D>(
     res: &mut impl BigNumMut<Digit = D>,
+    top: &mut D,
     lhs: &impl BigNum<Digit = D>,
     d: D,
     offset: usize,
-) -> D
+) -> bool
 where
     D: UnsignedNumDigit,
 {
-    assert!(res.len() >= lhs.len());
+    if d == D::ZERO {
+        return false;
+    }
+
     let mut carry = D::ZERO;
 
-    let mut i = offset;
+    let mut i = 0;
     let mut j = offset;
-    while i < lhs.len() {
-        let l = lhs[i];
-
-        let (n, m) = l.mul_to_parts(d);
-        debug_assert!(n < D::MAX);
-
-        let mut new_carry = D::ZERO;
-        let (z, overflow) = res[j].overflowing_add(m);
-        if overflow {
-            new_carry += D::ONE;
-        }
-
-        let (z, overflow) = z.overflowing_add(carry);
-        if overflow {
-            new_carry += D::ONE;
-        }
-
+    while i < lhs.len() && j < res.len() {
+        let (z, new_carry) = lhs[i].digit_carrying_mul(d, carry, res[j]);
         res[j] = z;
-        new_carry += n;
         carry = new_carry;
 
         j += 1;
@@ -47,21 +42,126 @@ where
         j += 1;
     }
 
-    carry
+    if j == res.len() {
+        let digit = if i < lhs.len() { lhs[i] } else { D::ZERO };
+        let (z, new_carry) = digit.digit_carrying_mul(d, carry, *top);
+        *top = z;
+        carry = new_carry;
+        i += 1;
+    }
+
+    // digits of lhs, which would be multiplied into positions above top
+    let mut lost = carry != D::ZERO;
+    while i < lhs.len() {
+        lost |= lhs[i] != D::ZERO;
+        i += 1;
+    }
+    lost
+}
+
+/// Assigns value of `src` to `res`, resizing `res` when it's too small.
+fn assign_resize<D, M>(res: &mut M, src: &impl BigNum<Digit = D>)
+where
+    M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+    D: UnsignedNumDigit,
+{
+    let size = src.significant_len();
+    if res.len() < size {
+        res.resize(size);
+    }
+    for i in 0..res.len() {
+        res[i] = if i < size { src[i] } else { D::ZERO };
+    }
+}
+
+/// Returns digit `k` of number shifted left by `shift` bits, where `shift` is smaller than digit size.
+/// Digits of number are returned by `get`.
+fn normalized_digit<D>(get: impl Fn(usize) -> D, k: usize, shift: u32) -> D
+where
+    D: UnsignedNumDigit,
+{
+    if shift == 0 {
+        get(k)
+    } else if k == 0 {
+        get(k) << shift
+    } else {
+        (get(k) << shift) | (get(k - 1) >> (D::NUM_BITS - shift))
+    }
+}
+
+/// Reverses order of digits of `res` in range `start..end`.
+fn reverse_digits<D>(res: &mut impl BigNumMut<Digit = D>, start: usize, end: usize)
+where
+    D: UnsignedNumDigit,
+{
+    let (mut i, mut j) = (start, end);
+    while i + 1 < j {
+        j -= 1;
+        let tmp = res[i];
+        res[i] = res[j];
+        res[j] = tmp;
+        i += 1;
+    }
 }
 
 /// Simple engine performs all operations in simplest possible way without any allocations on heap.
 pub struct SimpleEngine {}
 impl UnsignedEngine for SimpleEngine {
+    fn compare<D>(lhs: &impl BigNum<Digit = D>, rhs: &impl BigNum<Digit = D>) -> Ordering
+    where
+        D: UnsignedNumDigit,
+    {
+        // 1. Digits which only one of numbers has must be zero, otherwise that number is bigger
+        for i in (rhs.len()..lhs.len()).rev() {
+            if lhs[i] != D::ZERO {
+                return Ordering::Greater;
+            }
+        }
+        for i in (lhs.len()..rhs.len()).rev() {
+            if rhs[i] != D::ZERO {
+                return Ordering::Less;
+            }
+        }
+
+        // 2. Compare common digits starting from the most significant one
+        for i in (0..lhs.len().min(rhs.len())).rev() {
+            match lhs[i].cmp(&rhs[i]) {
+                Ordering::Equal => {}
+                o => {
+                    return o;
+                }
+            }
+        }
+
+        Ordering::Equal
+    }
+
     fn add_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>) -> bool
     where
         D: UnsignedNumDigit,
     {
-        assert!(res.len() >= rhs.len());
+        Self::add_with_carry(res, rhs, false)
+    }
+
+    fn sub_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        Self::sub_with_borrow(res, rhs, false)
+    }
+
+    fn add_with_carry<D>(
+        res: &mut impl BigNumMut<Digit = D>,
+        rhs: &impl BigNum<Digit = D>,
+        carry_in: bool,
+    ) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
         let mut i = 0;
         let mut j = 0;
 
-        let mut carry = false;
+        let mut carry = carry_in;
         while i < res.len() && j < rhs.len() {
             let l = res[i];
             let r = rhs[j];
@@ -85,27 +185,22 @@ impl UnsignedEngine for SimpleEngine {
             res[i] = n;
             i += 1;
         }
-        /*
-
-        wile j < rhs.len() && !carry {
-            carry = rhs[j] != D::ZERO;
-            j += 1;
-        }
-        */
-
-        carry
+        // digits of rhs, which res does not have, must be zeros
+        carry || rhs.significant_len() > res.len()
     }
 
-    fn sub_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>) -> bool
+    fn sub_with_borrow<D>(
+        res: &mut impl BigNumMut<Digit = D>,
+        rhs: &impl BigNum<Digit = D>,
+        borrow_in: bool,
+    ) -> bool
     where
         D: UnsignedNumDigit,
     {
-        assert!(res.len() >= rhs.len());
-
         let mut i = 0;
         let mut j = 0;
 
-        let mut borrow = false;
+        let mut borrow = borrow_in;
         while i < res.len() && j < rhs.len() {
             let l = res[i];
             let r = rhs[j];
@@ -120,18 +215,6 @@ impl UnsignedEngine for SimpleEngine {
             j += 1;
         }
 
-        // note: only one of blocks below will be executed
-        // as at least one of conditions i == res.len() or j == rhs.len() is met
-        // note #2: with assert above only j == rhs.len() can be met
-
-        /*
-        let mut fits = true;
-        while j < rhs.len() && fits {
-            fits = rhs[j] == D::ZERO;
-            j += 1;
-        }
-        */
-
         while i < res.len() && borrow {
             let (n, overflow) = res[i].overflowing_sub(if borrow { D::ONE } else { D::ZERO });
             borrow = overflow;
@@ -139,18 +222,137 @@ impl UnsignedEngine for SimpleEngine {
             i += 1;
         }
 
-        borrow
+        // digits of rhs, which res does not have, must be zeros
+        borrow || rhs.significant_len() > res.len()
+    }
+
+    fn add_digit<D>(res: &mut impl BigNumMut<Digit = D>, d: D) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        let mut carry = d;
+        let mut i = 0;
+        while i < res.len() && carry != D::ZERO {
+            let (n, overflow) = res[i].overflowing_add(carry);
+            res[i] = n;
+            carry = if overflow { D::ONE } else { D::ZERO };
+            i += 1;
+        }
+        carry != D::ZERO
+    }
+
+    fn sub_digit<D>(res: &mut impl BigNumMut<Digit = D>, d: D) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        let mut borrow = d;
+        let mut i = 0;
+        while i < res.len() && borrow != D::ZERO {
+            let (n, overflow) = res[i].overflowing_sub(borrow);
+            res[i] = n;
+            borrow = if overflow { D::ONE } else { D::ZERO };
+            i += 1;
+        }
+        borrow != D::ZERO
+    }
+
+    fn mul_add_digit<D>(res: &mut impl BigNumMut<Digit = D>, m: D, a: D) -> D
+    where
+        D: UnsignedNumDigit,
+    {
+        let mut carry = a;
+        for i in 0..res.len() {
+            let (lo, hi) = res[i].digit_carrying_mul(m, carry, D::ZERO);
+            res[i] = lo;
+            carry = hi;
+        }
+        carry
+    }
+
+    fn div_rem_digit<D>(res: &mut impl BigNumMut<Digit = D>, d: D) -> D
+    where
+        D: UnsignedNumDigit,
+    {
+        assert!(d != D::ZERO, "Division by zero");
+
+        let mut rem = D::ZERO;
+        for i in (0..res.len()).rev() {
+            let (quot, r) = rem.div_wide(res[i], d);
+            res[i] = quot;
+            rem = r;
+        }
+        rem
+    }
+
+    fn rem_digit<D>(lhs: &impl BigNum<Digit = D>, d: D) -> D
+    where
+        D: UnsignedNumDigit,
+    {
+        assert!(d != D::ZERO, "Division by zero");
+
+        let mut rem = D::ZERO;
+        for i in (0..lhs.len()).rev() {
+            let (_, r) = rem.div_wide(lhs[i], d);
+            rem = r;
+        }
+        rem
+    }
+
+    fn compare_u128<D>(lhs: &impl BigNum<Digit = D>, rhs: u128) -> Ordering
+    where
+        D: UnsignedNumDigit,
+    {
+        // amount of digits required to store any u128
+        let rhs_len = (128 / D::NUM_BITS).max(1) as usize;
+        let mask = if D::NUM_BITS >= 128 {
+            u128::MAX
+        } else {
+            (1u128 << D::NUM_BITS) - 1
+        };
+
+        for i in (rhs_len..lhs.len()).rev() {
+            if lhs[i] != D::ZERO {
+                return Ordering::Greater;
+            }
+        }
+
+        for i in (0..rhs_len).rev() {
+            let l = if i < lhs.len() { lhs[i] } else { D::ZERO };
+            let r = <D as TryFrom<u128>>::try_from(
+                rhs.checked_shr(i as u32 * D::NUM_BITS).unwrap_or(0) & mask,
+            )
+            .unwrap_or_else(|_| unreachable!());
+            match l.cmp(&r) {
+                Ordering::Equal => {}
+                o => {
+                    return o;
+                }
+            }
+        }
+
+        Ordering::Equal
     }
 
     fn shift_left_u32<D>(res: &mut impl BigNumMut<Digit = D>, n: u32) -> bool
     where
         D: UnsignedNumDigit,
     {
-        let ov_bytes = (n / D::NUM_BITS) as usize;
+        Self::shift_left_u64(res, n as u64)
+    }
 
-        let n = n % (res.len() as u32 * D::NUM_BITS);
-        let bytes = (n / D::NUM_BITS) as usize;
-        let bits = (n % D::NUM_BITS) as usize;
+    fn shift_left_u64<D>(res: &mut impl BigNumMut<Digit = D>, n: u64) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        if n >= (res.len() as u64).saturating_mul(D::NUM_BITS as u64) {
+            for i in 0..res.len() {
+                res[i] = D::ZERO;
+            }
+            return true;
+        }
+
+        let bytes = (n / D::NUM_BITS as u64) as usize;
+        let bits = (n % D::NUM_BITS as u64) as usize;
 
         // 1. Shift bytes
         // well, shifting left in little endian is shifting right actually...
@@ -181,18 +383,29 @@ impl UnsignedEngine for SimpleEngine {
             }
         }
 
-        ov_bytes >= res.len()
+        false
     }
 
     fn shift_right_u32<D>(res: &mut impl BigNumMut<Digit = D>, n: u32) -> bool
     where
         D: UnsignedNumDigit,
     {
-        let ov_bytes = (n / D::NUM_BITS) as usize;
+        Self::shift_right_u64(res, n as u64)
+    }
+
+    fn shift_right_u64<D>(res: &mut impl BigNumMut<Digit = D>, n: u64) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        if n >= (res.len() as u64).saturating_mul(D::NUM_BITS as u64) {
+            for i in 0..res.len() {
+                res[i] = D::ZERO;
+            }
+            return true;
+        }
 
-        let n = n % (res.len() as u32 * D::NUM_BITS);
-        let bytes = (n / D::NUM_BITS) as usize;
-        let bits = (n % D::NUM_BITS) as usize;
+        let bytes = (n / D::NUM_BITS as u64) as usize;
+        let bits = (n % D::NUM_BITS as u64) as usize;
 
         // 1. Shift bytes
         // well, shifting left in little endian is shifting right actually...
@@ -221,7 +434,32 @@ impl UnsignedEngine for SimpleEngine {
             }
         }
 
-        ov_bytes >= res.len()
+        false
+    }
+
+    fn rotate_left<D>(res: &mut impl BigNumMut<Digit = D>, n: usize)
+    where
+        D: UnsignedNumDigit,
+    {
+        let total_bits = res.len() * D::NUM_BITS as usize;
+        if total_bits == 0 {
+            return;
+        }
+        let n = n % total_bits;
+        let digits = n / D::NUM_BITS as usize;
+        let bits = (n % D::NUM_BITS as usize) as u32;
+
+        // 1. Rotate digits towards more significant positions, using three reversals
+        reverse_digits(res, 0, res.len());
+        reverse_digits(res, 0, digits);
+        reverse_digits(res, digits, res.len());
+
+        // 2. Rotate bits; bits shifted out of the most significant digit go to the least significant one
+        if bits > 0 {
+            let top = res[res.len() - 1] >> (D::NUM_BITS - bits);
+            Self::shift_left_u32(res, bits);
+            res[0] |= top;
+        }
     }
 
     fn mul_accumulate<D>(
@@ -235,29 +473,49 @@ impl UnsignedEngine for SimpleEngine {
         // only simple multiplication algortihm
         let mut overflow = false;
         for i in 0..rhs.len() {
-            overflow |= mul_accumulate_digit(res, lhs, rhs[i], i) != D::ZERO;
+            let mut top = D::ZERO;
+            let lost = mul_accumulate_digit(res, &mut top, lhs, rhs[i], i);
+            overflow |= top != D::ZERO || lost;
         }
         overflow
     }
 
+    fn mul_accumulate_carry<D>(
+        res: &mut impl BigNumMut<Digit = D>,
+        lhs: &impl BigNum<Digit = D>,
+        rhs: &impl BigNum<Digit = D>,
+    ) -> D
+    where
+        D: UnsignedNumDigit,
+    {
+        // res is treated as if it had one more digit, which is returned
+        let mut top = D::ZERO;
+        let mut fits = true;
+        for i in 0..rhs.len() {
+            fits &= !mul_accumulate_digit(res, &mut top, lhs, rhs[i], i);
+        }
+
+        assert!(fits, "Overflowing part of result does not fit single digit");
+        top
+    }
+
     fn add_resize<D, M>(res: &mut M, rhs: &impl BigNum<Digit = D>)
     where
         M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
         D: UnsignedNumDigit,
     {
-        if res.len() < rhs.len() {
-            res.resize(rhs.len());
+        // grow once, with one more digit for carry
+        let original_size = res.len();
+        let size = res.significant_len().max(rhs.significant_len()) + 1;
+        if original_size < size {
+            res.resize(size);
         }
 
         let carry = Self::add_accumulate(res, rhs);
-        if carry {
-            res.resize(res.len() + 1);
-            let res_sz = res.len();
+        debug_assert!(!carry);
 
-            let dst = &mut res[res_sz - 1];
-            debug_assert!(*dst == D::ZERO);
-            *dst = D::ONE;
-        }
+        // drop digits, which turned out to be not needed
+        res.truncate(res.significant_len().max(original_size));
     }
 
     fn sub_resize<D, M>(res: &mut M, rhs: &impl BigNum<Digit = D>) -> bool
@@ -265,8 +523,9 @@ impl UnsignedEngine for SimpleEngine {
         M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
         D: UnsignedNumDigit,
     {
-        if res.len() < rhs.len() {
-            res.resize(rhs.len());
+        let rhs_real_size = rhs.significant_len();
+        if res.len() < rhs_real_size {
+            res.resize(rhs_real_size);
         }
 
         // note: in sub carry can't be handled as it potentially can set infinite 0xff bytes in order to express minus number
@@ -280,27 +539,358 @@ impl UnsignedEngine for SimpleEngine {
         M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
         D: UnsignedNumDigit,
     {
-        let mut lhs_real_size = lhs.len();
-        for i in (0..lhs.len()).rev() {
-            if lhs[i] != D::ZERO {
-                break;
+        let lhs_real_size = lhs.significant_len();
+        let rhs_real_size = rhs.significant_len();
+
+        // value of res is added to the product, so it may need one more digit for carry
+        let original_size = res.len();
+        let size = res.significant_len().max(lhs_real_size + rhs_real_size) + 1;
+        if original_size < size {
+            res.resize(size);
+        }
+
+        let overflow = Self::mul_accumulate(res, lhs, rhs);
+        debug_assert!(!overflow);
+
+        // drop digits, which turned out to be not needed
+        res.truncate(res.significant_len().max(original_size));
+    }
+
+    fn div_rem<D>(
+        quot: &mut impl BigNumMut<Digit = D>,
+        rem: &mut impl BigNumMut<Digit = D>,
+        lhs: &impl BigNum<Digit = D>,
+        rhs: &impl BigNum<Digit = D>,
+    ) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        let rhs_real_size = rhs.significant_len();
+        assert!(rhs_real_size > 0, "Division by zero");
+        assert!(
+            rem.len() >= rhs_real_size,
+            "Rem must be able to store every value smaller than rhs"
+        );
+
+        for i in 0..quot.len() {
+            quot[i] = D::ZERO;
+        }
+        for i in 0..rem.len() {
+            rem[i] = D::ZERO;
+        }
+
+        // divisor with single digit, so quotient can be computed digit by digit
+        if rhs_real_size == 1 {
+            let d = rhs[0];
+            let mut overflow = false;
+            let mut r = D::ZERO;
+            for i in (0..lhs.len()).rev() {
+                let (q, next) = r.div_wide(lhs[i], d);
+                if i < quot.len() {
+                    quot[i] = q;
+                } else {
+                    overflow |= q != D::ZERO;
+                }
+                r = next;
             }
-            lhs_real_size -= 1;
+            rem[0] = r;
+            return overflow;
         }
 
-        let mut rhs_real_size = rhs.len();
-        for i in (0..rhs.len()).rev() {
-            if rhs[i] != D::ZERO {
-                break;
+        // schoolbook division(Knuth's algorithm D), one digit of quotient at a time
+        // normalization is done on the fly, so neither rhs nor remainder has to be copied
+        let n = rhs_real_size;
+        let shift = rhs[n - 1].leading_zeros();
+        let v1 = normalized_digit(|k| rhs[k], n - 1, shift);
+        let v2 = normalized_digit(|k| rhs[k], n - 2, shift);
+
+        let mut overflow = false;
+        for i in (0..lhs.significant_len()).rev() {
+            // shift remainder by one digit and bring next digit of lhs
+            // remainder is smaller than rhs, so after shift it has at most n + 1 digits
+            let mut top = rem[n - 1];
+            for k in (1..n).rev() {
+                rem[k] = rem[k - 1];
+            }
+            rem[0] = lhs[i];
+
+            // estimate digit of quotient using three most significant digits of remainder
+            let (u0, u1, u2) = {
+                let get = |k: usize| if k == n { top } else { rem[k] };
+                (
+                    normalized_digit(get, n, shift),
+                    normalized_digit(get, n - 1, shift),
+                    normalized_digit(get, n - 2, shift),
+                )
+            };
+
+            let (mut qhat, mut rhat, mut rhat_overflow) = if u0 >= v1 {
+                let (rhat, overflow) = u1.overflowing_add(v1);
+                (D::MAX, rhat, overflow)
+            } else {
+                let (qhat, rhat) = u0.div_wide(u1, v1);
+                (qhat, rhat, false)
+            };
+            while !rhat_overflow && qhat.mul_to_parts(v2) > (rhat, u2) {
+                qhat -= D::ONE;
+                let (r, overflow) = rhat.overflowing_add(v1);
+                rhat = r;
+                rhat_overflow = overflow;
+            }
+
+            // subtract qhat * rhs from remainder
+            let mut carry = D::ZERO;
+            let mut borrow = false;
+            for k in 0..n {
+                let (lo, hi) = rhs[k].digit_carrying_mul(qhat, carry, D::ZERO);
+                let (d, b) = rem[k].digit_borrowing_sub(lo, borrow);
+                rem[k] = d;
+                carry = hi;
+                borrow = b;
+            }
+            let (t, negative) = top.digit_borrowing_sub(carry, borrow);
+            top = t;
+
+            // qhat was by one too big, so add rhs back
+            if negative {
+                qhat -= D::ONE;
+                let mut carry = false;
+                for k in 0..n {
+                    let (d, c) = rem[k].digit_carrying_add(rhs[k], carry);
+                    rem[k] = d;
+                    carry = c;
+                }
+                top = top.wrapping_add(if carry { D::ONE } else { D::ZERO });
+            }
+            debug_assert!(top == D::ZERO);
+
+            if i < quot.len() {
+                quot[i] = qhat;
+            } else {
+                overflow |= qhat != D::ZERO;
             }
-            rhs_real_size -= 1;
         }
 
-        if res.len() < lhs_real_size + rhs_real_size {
-            res.resize(lhs_real_size + rhs_real_size);
+        overflow
+    }
+
+    fn div_rem_resize<D, M>(
+        quot: &mut M,
+        rem: &mut M,
+        lhs: &impl BigNum<Digit = D>,
+        rhs: &impl BigNum<Digit = D>,
+    ) where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        let lhs_real_size = lhs.significant_len();
+        let rhs_real_size = rhs.significant_len();
+
+        if quot.len() < lhs_real_size {
+            quot.resize(lhs_real_size);
+        }
+        if rem.len() < rhs_real_size {
+            rem.resize(rhs_real_size);
         }
 
-        let overflow = Self::mul_accumulate(res, lhs, rhs);
+        let overflow = Self::div_rem(quot, rem, lhs, rhs);
+        debug_assert!(!overflow);
+    }
+
+    fn pow_mod_resize<D, M>(
+        res: &mut M,
+        base: &impl BigNum<Digit = D>,
+        exp: &impl BigNum<Digit = D>,
+        modulus: &impl BigNum<Digit = D>,
+    ) where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        let size = modulus.significant_len();
+        assert!(size > 0, "Division by zero");
+
+        // all temporaries are allocated once
+        let mut acc = M::new_zeroed_sized(size);
+        let mut power = M::new_zeroed_sized(size);
+        let mut prod = M::new_zeroed_sized(2 * size);
+        let mut quot = M::new_zeroed_sized(base.len().max(2 * size));
+
+        if !modulus.is_one() {
+            acc[0] = D::ONE;
+        }
+        let overflow = Self::div_rem(&mut quot, &mut power, base, modulus);
+        debug_assert!(!overflow);
+
+        // left to right square and multiply
+        for bit in (0..exp.bit_len()).rev() {
+            for i in 0..prod.len() {
+                prod[i] = D::ZERO;
+            }
+            Self::mul_accumulate(&mut prod, &acc, &acc);
+            Self::div_rem(&mut quot, &mut acc, &prod, modulus);
+
+            if exp.test_bit(bit) {
+                for i in 0..prod.len() {
+                    prod[i] = D::ZERO;
+                }
+                Self::mul_accumulate(&mut prod, &acc, &power);
+                Self::div_rem(&mut quot, &mut acc, &prod, modulus);
+            }
+        }
+
+        assign_resize(res, &acc);
+    }
+
+    fn gcd_resize<D, M>(res: &mut M, lhs: &impl BigNum<Digit = D>, rhs: &impl BigNum<Digit = D>)
+    where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        let mut a = M::new_zeroed();
+        let mut b = M::new_zeroed();
+        assign_resize(&mut a, lhs);
+        assign_resize(&mut b, rhs);
+
+        // Euclid's algorithm
+        let mut quot = M::new_zeroed();
+        let mut rem = M::new_zeroed();
+        while !b.is_zero() {
+            Self::div_rem_resize(&mut quot, &mut rem, &a, &b);
+            core::mem::swap(&mut a, &mut b);
+            core::mem::swap(&mut b, &mut rem);
+        }
+        assign_resize(res, &a);
+    }
+
+    fn inv_mod_resize<D, M>(
+        res: &mut M,
+        value: &impl BigNum<Digit = D>,
+        modulus: &impl BigNum<Digit = D>,
+    ) -> bool
+    where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        assert!(!modulus.is_zero(), "Division by zero");
+
+        // extended Euclid's algorithm, where coefficients are kept modulo `modulus`,
+        // so no signed numbers are needed
+        let mut quot = M::new_zeroed();
+        let mut r0 = M::new_zeroed();
+        let mut r1 = M::new_zeroed();
+        assign_resize(&mut r0, modulus);
+        Self::div_rem_resize(&mut quot, &mut r1, value, modulus);
+
+        let mut t0 = M::new_zeroed();
+        let mut t1 = M::new_zeroed_sized(1);
+        t1[0] = D::ONE;
+
+        let mut rem = M::new_zeroed();
+        let mut prod = M::new_zeroed();
+        let mut prod_rem = M::new_zeroed();
+        while !r1.is_zero() {
+            Self::div_rem_resize(&mut quot, &mut rem, &r0, &r1);
+
+            // t2 = (t0 - quot * t1) mod modulus
+            for i in 0..prod.len() {
+                prod[i] = D::ZERO;
+            }
+            Self::mul_resize(&mut prod, &quot, &t1);
+            Self::div_rem_resize(&mut quot, &mut prod_rem, &prod, modulus);
+            if Self::compare(&t0, &prod_rem) == Ordering::Less {
+                Self::add_resize(&mut t0, modulus);
+            }
+            let borrow = Self::sub_resize(&mut t0, &prod_rem);
+            debug_assert!(!borrow);
+
+            core::mem::swap(&mut r0, &mut r1);
+            core::mem::swap(&mut r1, &mut rem);
+            core::mem::swap(&mut t0, &mut t1);
+        }
+
+        if !r0.is_one() {
+            return false;
+        }
+        // inverse modulo one is zero
+        if modulus.is_one() {
+            t0 = M::new_zeroed();
+        }
+        assign_resize(res, &t0);
+        true
+    }
+
+    fn and_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>)
+    where
+        D: UnsignedNumDigit,
+    {
+        for i in 0..res.len() {
+            res[i] &= if i < rhs.len() { rhs[i] } else { D::ZERO };
+        }
+    }
+
+    fn and_not_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>)
+    where
+        D: UnsignedNumDigit,
+    {
+        for i in 0..res.len().min(rhs.len()) {
+            res[i] &= !rhs[i];
+        }
+    }
+
+    fn or_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        for i in 0..res.len().min(rhs.len()) {
+            res[i] |= rhs[i];
+        }
+        rhs.significant_len() > res.len()
+    }
+
+    fn xor_accumulate<D>(res: &mut impl BigNumMut<Digit = D>, rhs: &impl BigNum<Digit = D>) -> bool
+    where
+        D: UnsignedNumDigit,
+    {
+        for i in 0..res.len().min(rhs.len()) {
+            res[i] ^= rhs[i];
+        }
+        rhs.significant_len() > res.len()
+    }
+
+    fn not_in_place<D>(res: &mut impl BigNumMut<Digit = D>)
+    where
+        D: UnsignedNumDigit,
+    {
+        for i in 0..res.len() {
+            res[i] = !res[i];
+        }
+    }
+
+    fn or_resize<D, M>(res: &mut M, rhs: &impl BigNum<Digit = D>)
+    where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        let rhs_real_size = rhs.significant_len();
+        if res.len() < rhs_real_size {
+            res.resize(rhs_real_size);
+        }
+
+        let overflow = Self::or_accumulate(res, rhs);
+        debug_assert!(!overflow);
+    }
+
+    fn xor_resize<D, M>(res: &mut M, rhs: &impl BigNum<Digit = D>)
+    where
+        M: DynamicBigNum<Digit = D> + BigNumMut<Digit = D>,
+        D: UnsignedNumDigit,
+    {
+        let rhs_real_size = rhs.significant_len();
+        if res.len() < rhs_real_size {
+            res.resize(rhs_real_size);
+        }
+
+        let overflow = Self::xor_accumulate(res, rhs);
         debug_assert!(!overflow);
     }
 }
@@ -308,12 +898,13 @@ impl UnsignedEngine for SimpleEngine {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::iutil::xorshift;
     use crate::num::VecBigNum;
 
     #[test]
     fn test_add_accumulate_u16() {
-        for a in 0..(std::u16::MAX) {
-            for b in 0..(std::u16::MAX >> 8) {
+        for a in 0..u16::MAX {
+            for b in 0..(u16::MAX >> 8) {
                 let bn_a = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
                 let bn_b = VecBigNum::from(Vec::from(&b.to_le_bytes()[..]));
                 let (c, overflow) = a.overflowing_add(b);
@@ -333,8 +924,8 @@ mod test {
 
     #[test]
     fn test_sub_accumulate_u16() {
-        for a in 0..(std::u16::MAX) {
-            for b in 0..(std::u16::MAX >> 8) {
+        for a in 0..u16::MAX {
+            for b in 0..(u16::MAX >> 8) {
                 let bn_a = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
                 let bn_b = VecBigNum::from(Vec::from(&b.to_le_bytes()[..]));
                 let (c, overflow) = a.overflowing_sub(b);
@@ -354,8 +945,8 @@ mod test {
 
     #[test]
     fn test_mul_accumulate_u16() {
-        for a in 0..(std::u16::MAX) {
-            for b in 0..(std::u16::MAX >> 8) {
+        for a in 0..u16::MAX {
+            for b in 0..(u16::MAX >> 8) {
                 let bn_a = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
                 let bn_b = VecBigNum::from(Vec::from(&b.to_le_bytes()[..]));
                 let (c, overflow) = a.overflowing_mul(b);
@@ -370,12 +961,110 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_mul_accumulate_u16_both_digits() {
+        for a in (0..u16::MAX).step_by(7) {
+            for b in (0..u16::MAX).step_by(1031) {
+                let bn_a = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
+                let bn_b = VecBigNum::from(Vec::from(&b.to_le_bytes()[..]));
+                let c = a as u32 * b as u32;
+
+                let mut res: VecBigNum<u8> = VecBigNum::new_zeroed();
+                SimpleEngine::mul_resize(&mut res, &bn_a, &bn_b);
+
+                let mut expected = VecBigNum::from(Vec::from(&c.to_le_bytes()[..]));
+                expected.resize(res.len());
+                assert_eq!(res.into_inner(), expected.into_inner());
+            }
+        }
+    }
+
+    #[test]
+    fn test_div_rem_u16() {
+        for a in (0..u16::MAX).step_by(13) {
+            for b in (1..u16::MAX).step_by(997) {
+                let bn_a = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
+                let bn_b = VecBigNum::from(Vec::from(&b.to_le_bytes()[..]));
+
+                let mut quot: VecBigNum<u8> = VecBigNum::new_zeroed();
+                let mut rem: VecBigNum<u8> = VecBigNum::new_zeroed();
+                SimpleEngine::div_rem_resize(&mut quot, &mut rem, &bn_a, &bn_b);
+                quot.resize(2);
+                rem.resize(2);
+
+                assert_eq!(&quot.into_inner()[..], &(a / b).to_le_bytes()[..]);
+                assert_eq!(&rem.into_inner()[..], &(a % b).to_le_bytes()[..]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_div_rem_overflow() {
+        let lhs = VecBigNum::from(vec![0u8, 0, 1]);
+        let rhs = VecBigNum::from(vec![2u8]);
+        let mut quot = VecBigNum::<u8>::new_zeroed_sized(1);
+        let mut rem = VecBigNum::<u8>::new_zeroed_sized(1);
+
+        assert!(SimpleEngine::div_rem(&mut quot, &mut rem, &lhs, &rhs));
+        assert_eq!(rem.into_inner(), vec![0]);
+
+        let mut rem = VecBigNum::<u8>::new_zeroed_sized(3);
+        assert!(!SimpleEngine::div_rem(&mut quot, &mut rem, &rhs, &lhs));
+        assert_eq!(quot.into_inner(), vec![0]);
+        assert_eq!(rem.into_inner(), vec![2, 0, 0]);
+    }
+
+    #[test]
+    fn test_bitwise_different_lengths() {
+        for a in (0..u16::MAX).step_by(97) {
+            for b in (0..u16::MAX).step_by(89) {
+                // rhs has single digit; it's zero extended to two digits of res
+                let short_b = b & 0xff;
+                let bn_a = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
+                let bn_b = VecBigNum::from(vec![short_b as u8]);
+
+                let mut res = bn_a.clone();
+                SimpleEngine::and_accumulate(&mut res, &bn_b);
+                assert_eq!(&res.into_inner()[..], &(a & short_b).to_le_bytes()[..]);
+
+                let mut res = bn_a.clone();
+                SimpleEngine::and_not_accumulate(&mut res, &bn_b);
+                assert_eq!(&res.into_inner()[..], &(a & !short_b).to_le_bytes()[..]);
+
+                let mut res = bn_a.clone();
+                assert!(!SimpleEngine::or_accumulate(&mut res, &bn_b));
+                assert_eq!(&res.into_inner()[..], &(a | short_b).to_le_bytes()[..]);
+
+                let mut res = bn_a.clone();
+                assert!(!SimpleEngine::xor_accumulate(&mut res, &bn_b));
+                assert_eq!(&res.into_inner()[..], &(a ^ short_b).to_le_bytes()[..]);
+
+                let mut res = bn_a.clone();
+                SimpleEngine::not_in_place(&mut res);
+                assert_eq!(&res.into_inner()[..], &(!a).to_le_bytes()[..]);
+
+                // res is shorter than rhs
+                let bn_b = VecBigNum::from(Vec::from(&b.to_le_bytes()[..]));
+                let mut res = VecBigNum::from(vec![a as u8]);
+                assert_eq!(SimpleEngine::xor_accumulate(&mut res, &bn_b), b > 0xff);
+                assert_eq!(res.into_inner(), vec![(a as u8) ^ (b as u8)]);
+
+                let mut res = VecBigNum::from(vec![a as u8]);
+                SimpleEngine::or_resize(&mut res, &bn_b);
+                assert_eq!(
+                    res,
+                    VecBigNum::from(Vec::from(&((a & 0xff) | b).to_le_bytes()[..]))
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_shift_left_u32() {
-        for a in 0..(std::u16::MAX) {
+        for a in 0..u16::MAX {
             for b in 0..20u32 {
                 let bn_a = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
-                let (c, overflow) = a.overflowing_shl(b);
+                let (c, overflow) = (a.checked_shl(b).unwrap_or(0), b >= 16);
 
                 let mut res: VecBigNum<u8> =
                     VecBigNum::new_zeroed_sized(core::mem::size_of::<u16>());
@@ -390,10 +1079,10 @@ mod test {
 
     #[test]
     fn test_shift_right_u32() {
-        for a in 0..(std::u16::MAX) {
+        for a in 0..u16::MAX {
             for b in 0..20u32 {
                 let bn_a = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
-                let (c, overflow) = a.overflowing_shr(b);
+                let (c, overflow) = (a.checked_shr(b).unwrap_or(0), b >= 16);
 
                 let mut res: VecBigNum<u8> =
                     VecBigNum::new_zeroed_sized(core::mem::size_of::<u16>());
@@ -405,4 +1094,393 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_shift_large_counts() {
+        let mut res = VecBigNum::from(vec![0xffu8, 0xff, 0xff]);
+        assert!(!SimpleEngine::shift_left_u32(&mut res, 23));
+        assert_eq!(res, VecBigNum::from(vec![0u8, 0, 0x80]));
+        assert!(SimpleEngine::shift_left_u32(&mut res, 24));
+        assert_eq!(res, VecBigNum::from(vec![0u8, 0, 0]));
+
+        let mut res = VecBigNum::from(vec![0xffu8, 0xff, 0xff]);
+        assert!(SimpleEngine::shift_right_u32(&mut res, u32::MAX));
+        assert_eq!(res, VecBigNum::from(vec![0u8, 0, 0]));
+
+        let mut empty = VecBigNum::<u8>::new_zeroed();
+        assert!(SimpleEngine::shift_right_u32(&mut empty, 1));
+        SimpleEngine::rotate_left(&mut empty, 1);
+    }
+
+    #[test]
+    fn test_rotate_u32() {
+        for &a in [0u32, 1, 0x8000_0001, 0x1234_5678, 0xdead_beef, u32::MAX - 1].iter() {
+            for n in 0..100usize {
+                let mut res = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
+                SimpleEngine::rotate_left(&mut res, n);
+                assert_eq!(
+                    &res.into_inner()[..],
+                    &a.rotate_left(n as u32).to_le_bytes()[..]
+                );
+
+                let mut res = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
+                SimpleEngine::rotate_right(&mut res, n);
+                assert_eq!(
+                    &res.into_inner()[..],
+                    &a.rotate_right(n as u32).to_le_bytes()[..]
+                );
+
+                let mut res = VecBigNum::from(vec![a as u16, (a >> 16) as u16]);
+                SimpleEngine::rotate_left(&mut res, n);
+                let c = a.rotate_left(n as u32);
+                assert_eq!(res.into_inner(), vec![c as u16, (c >> 16) as u16]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shift_resize() {
+        let mut res = VecBigNum::from(vec![1u64]);
+        SimpleEngine::shift_left_resize(&mut res, 10_000_000);
+        assert_eq!(res.len(), 10_000_000 / 64 + 1);
+        assert_eq!(res.bit_len(), 10_000_001);
+        assert_eq!(BigNumBits::count_ones(&res), 1);
+
+        assert!(!SimpleEngine::shift_right_usize(&mut res, 10_000_000));
+        assert_eq!(res, VecBigNum::from(vec![1u64]));
+
+        let mut res = VecBigNum::from(vec![0xffu8, 0x81, 0, 0]);
+        SimpleEngine::shift_left_resize(&mut res, 9);
+        assert_eq!(res, VecBigNum::from(vec![0u8, 0xfe, 0x03, 0x01]));
+        SimpleEngine::shift_left_resize(&mut res, 7);
+        assert_eq!(res, VecBigNum::from(vec![0u8, 0, 0xff, 0x81, 0]));
+
+        let mut zero = VecBigNum::<u8>::new_zeroed();
+        SimpleEngine::shift_left_resize(&mut zero, u64::MAX);
+        assert!(zero.is_empty());
+    }
+
+    #[test]
+    fn test_resize_ops_do_not_leave_zero_digits() {
+        let mut res = VecBigNum::<u8>::new_zeroed();
+        for _ in 0..1000 {
+            SimpleEngine::add_resize(&mut res, &VecBigNum::from(vec![0xffu8, 0, 0, 0]));
+        }
+        assert_eq!(res.len(), 3);
+        assert_eq!(res, VecBigNum::from(vec![0x18u8, 0xe4, 0x03]));
+
+        let mut res = VecBigNum::<u8>::new_zeroed();
+        SimpleEngine::mul_resize(
+            &mut res,
+            &VecBigNum::from(vec![2u8, 0, 0]),
+            &VecBigNum::from(vec![3u8, 0]),
+        );
+        assert_eq!(res.into_inner(), vec![6]);
+
+        // digits which were there before are kept
+        let mut res = VecBigNum::from(vec![1u8, 0, 0, 0]);
+        SimpleEngine::add_resize(&mut res, &VecBigNum::from(vec![1u8]));
+        assert_eq!(res.into_inner(), vec![2, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_accumulate_longer_rhs() {
+        let mut res = VecBigNum::from(vec![1u8]);
+        assert!(!SimpleEngine::add_accumulate(
+            &mut res,
+            &VecBigNum::from(vec![1u8, 0])
+        ));
+        assert!(SimpleEngine::add_accumulate(
+            &mut res,
+            &VecBigNum::from(vec![1u8, 1])
+        ));
+        assert!(!SimpleEngine::sub_accumulate(
+            &mut res,
+            &VecBigNum::from(vec![1u8, 0])
+        ));
+        assert!(SimpleEngine::sub_accumulate(
+            &mut res,
+            &VecBigNum::from(vec![0u8, 1])
+        ));
+    }
+
+    #[test]
+    fn test_scalar_ops_u32() {
+        let values = [
+            0u32,
+            1,
+            0xff,
+            0x100,
+            0xffff,
+            0x1234_5678,
+            0xffff_fffe,
+            u32::MAX,
+        ];
+        for &a in values.iter() {
+            for d in (0..=255u8).step_by(17).chain(core::iter::once(255)) {
+                let from_u32 = |v: u32| VecBigNum::from(Vec::from(&v.to_le_bytes()[..]));
+
+                let mut res = from_u32(a);
+                let (c, overflow) = a.overflowing_add(d as u32);
+                assert_eq!(SimpleEngine::add_digit(&mut res, d), overflow);
+                assert_eq!(res, from_u32(c));
+
+                let mut res = from_u32(a);
+                let (c, overflow) = a.overflowing_sub(d as u32);
+                assert_eq!(SimpleEngine::sub_digit(&mut res, d), overflow);
+                assert_eq!(res, from_u32(c));
+
+                let mut res = from_u32(a);
+                let c = a as u64 * d as u64 + 7;
+                let carry = SimpleEngine::mul_add_digit(&mut res, d, 7);
+                assert_eq!(res, from_u32(c as u32));
+                assert_eq!(carry, (c >> 32) as u8);
+
+                let mut res = from_u32(a);
+                let c = a as u64 * d as u64;
+                assert_eq!(SimpleEngine::mul_digit(&mut res, d), (c >> 32) as u8);
+                assert_eq!(res, from_u32(c as u32));
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare_primitive() {
+        let values = [0u128, 1, 0xff, 0x100, u64::MAX as u128, 1 << 64, u128::MAX];
+        for &a in values.iter() {
+            for &b in values.iter() {
+                let bytes = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
+                assert_eq!(SimpleEngine::compare_u128(&bytes, b), a.cmp(&b));
+
+                let mut wide = VecBigNum::from(vec![a]);
+                assert_eq!(SimpleEngine::compare_u128(&wide, b), a.cmp(&b));
+                wide.push_digit(1);
+                assert_eq!(SimpleEngine::compare_u128(&wide, b), Ordering::Greater);
+            }
+            let short = VecBigNum::from(vec![a as u8]);
+            assert_eq!(
+                SimpleEngine::compare_u64(&short, a as u64),
+                (a as u8 as u64).cmp(&(a as u64))
+            );
+        }
+    }
+
+    #[test]
+    fn test_carry_chaining_split_numbers() {
+        let values = [
+            0u32,
+            1,
+            0xffff,
+            0x1_0000,
+            0x1234_5678,
+            0xffff_fffe,
+            u32::MAX,
+        ];
+        let split = |v: u32| {
+            let bytes = v.to_le_bytes();
+            (
+                VecBigNum::from(Vec::from(&bytes[..2])),
+                VecBigNum::from(Vec::from(&bytes[2..])),
+            )
+        };
+        let join = |lo: &VecBigNum<u8>, hi: &VecBigNum<u8>| {
+            u32::from_le_bytes([lo[0], lo[1], hi[0], hi[1]])
+        };
+
+        for &a in values.iter() {
+            for &b in values.iter() {
+                let (mut lo, mut hi) = split(a);
+                let (rhs_lo, rhs_hi) = split(b);
+                let carry = SimpleEngine::add_with_carry(&mut lo, &rhs_lo, false);
+                let carry = SimpleEngine::add_with_carry(&mut hi, &rhs_hi, carry);
+                let (c, overflow) = a.overflowing_add(b);
+                assert_eq!(join(&lo, &hi), c);
+                assert_eq!(carry, overflow);
+
+                let (mut lo, mut hi) = split(a);
+                let borrow = SimpleEngine::sub_with_borrow(&mut lo, &rhs_lo, false);
+                let borrow = SimpleEngine::sub_with_borrow(&mut hi, &rhs_hi, borrow);
+                let (c, overflow) = a.overflowing_sub(b);
+                assert_eq!(join(&lo, &hi), c);
+                assert_eq!(borrow, overflow);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_accumulate_carry() {
+        let from_u64 = |v: u64, size: usize| VecBigNum::from(Vec::from(&v.to_le_bytes()[..size]));
+        let values = [0u64, 1, 0xff, 0x100, 0xffff, 0x1234, 0xfffe];
+        for &init in values.iter() {
+            for &a in values.iter() {
+                for &b in values.iter() {
+                    // two digit res, so overflow has to fit third digit
+                    let c = init + a * b;
+                    if c >> 24 == 0 {
+                        let mut res = from_u64(init, 2);
+                        let overflow = SimpleEngine::mul_accumulate_carry(
+                            &mut res,
+                            &from_u64(a, 2),
+                            &from_u64(b, 2),
+                        );
+                        assert_eq!(res, from_u64(c, 2));
+                        assert_eq!(overflow as u64, c >> 16);
+                    }
+
+                    let mut res = from_u64(init, 4);
+                    let overflow = SimpleEngine::mul_accumulate_carry(
+                        &mut res,
+                        &from_u64(a, 2),
+                        &from_u64(b, 2),
+                    );
+                    assert_eq!(res, from_u64(c, 4));
+                    assert_eq!(overflow, 0);
+                }
+            }
+        }
+
+        // product starts right at the overflowing digit
+        let mut res = VecBigNum::from(vec![5u8]);
+        let overflow = SimpleEngine::mul_accumulate_carry(
+            &mut res,
+            &VecBigNum::from(vec![3u8]),
+            &VecBigNum::from(vec![0u8, 7]),
+        );
+        assert_eq!(res.into_inner(), vec![5]);
+        assert_eq!(overflow, 21);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mul_accumulate_carry_does_not_fit() {
+        let mut res = VecBigNum::from(vec![0u8]);
+        SimpleEngine::mul_accumulate_carry(
+            &mut res,
+            &VecBigNum::from(vec![0xffu8, 0xff]),
+            &VecBigNum::from(vec![0xffu8, 0xff]),
+        );
+    }
+
+    #[test]
+    fn test_div_rem_digit() {
+        let values = [0u32, 1, 0xff, 0x100, 0x1234_5678, 0xdead_beef, u32::MAX];
+        for &a in values.iter() {
+            for &d in [1u8, 2, 3, 10, 0x7f, 0xff].iter() {
+                let mut res = VecBigNum::from(Vec::from(&a.to_le_bytes()[..]));
+                let rem = SimpleEngine::div_rem_digit(&mut res, d);
+                assert_eq!(rem as u32, a % d as u32);
+                assert_eq!(
+                    res,
+                    VecBigNum::from(Vec::from(&(a / d as u32).to_le_bytes()[..]))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_div_rem_multi_digit() {
+        let from_u64 = |v: u64| VecBigNum::from(Vec::from(&v.to_le_bytes()[..]));
+        let mut state = 0x1234_5678_9abc_def0;
+        let mut values = vec![
+            1u64,
+            0xff,
+            0x100,
+            0xffff,
+            0x1_0000,
+            u64::MAX,
+            0x8000_0000_0000_0000,
+        ];
+        for _ in 0..200 {
+            let v = xorshift(&mut state);
+            // mix of sizes, so both quotient and divisor have various amount of digits
+            values.push((v >> (v % 64)).max(1));
+        }
+
+        for &a in values.iter() {
+            for &b in values.iter() {
+                let mut quot = VecBigNum::<u8>::new_zeroed_sized(8);
+                let mut rem = VecBigNum::<u8>::new_zeroed_sized(8);
+                let overflow =
+                    SimpleEngine::div_rem(&mut quot, &mut rem, &from_u64(a), &from_u64(b));
+                assert!(!overflow);
+                assert_eq!(quot, from_u64(a / b), "{} / {}", a, b);
+                assert_eq!(rem, from_u64(a % b), "{} % {}", a, b);
+                assert_eq!(
+                    SimpleEngine::rem_digit(&from_u64(a), (b % 255 + 1) as u8) as u64,
+                    a % (b % 255 + 1)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        let from_u64 = |v: u64| VecBigNum::from(Vec::from(&v.to_le_bytes()[..]));
+        let pow_mod = |b: u64, mut e: u64, m: u64| {
+            let (mut res, mut b) = (1 % m as u128, b as u128 % m as u128);
+            while e > 0 {
+                if e & 1 == 1 {
+                    res = res * b % m as u128;
+                }
+                b = b * b % m as u128;
+                e >>= 1;
+            }
+            res as u64
+        };
+
+        let mut state = 0xdead_beef_cafe_babe;
+        for _ in 0..200 {
+            let b = xorshift(&mut state);
+            let e = xorshift(&mut state) >> 48;
+            let m = xorshift(&mut state) >> (state % 64);
+            let m = m.max(1);
+
+            let mut res = VecBigNum::<u8>::new_zeroed();
+            SimpleEngine::pow_mod_resize(&mut res, &from_u64(b), &from_u64(e), &from_u64(m));
+            assert_eq!(res, from_u64(pow_mod(b, e, m)), "{} ^ {} mod {}", b, e, m);
+        }
+
+        let mut res = VecBigNum::<u8>::new_zeroed();
+        SimpleEngine::pow_mod_resize(&mut res, &from_u64(5), &from_u64(0), &from_u64(1));
+        assert!(res.is_zero());
+    }
+
+    #[test]
+    fn test_gcd_and_inverse() {
+        let from_u64 = |v: u64| VecBigNum::from(Vec::from(&v.to_le_bytes()[..]));
+        let gcd = |mut a: u64, mut b: u64| {
+            while b != 0 {
+                let r = a % b;
+                a = b;
+                b = r;
+            }
+            a
+        };
+
+        let mut state = 0x0bad_cafe_dead_beef;
+        for _ in 0..300 {
+            let a = xorshift(&mut state) >> (state % 64);
+            let m = (xorshift(&mut state) >> (state % 64)).max(1);
+
+            let mut res = VecBigNum::<u8>::new_zeroed();
+            SimpleEngine::gcd_resize(&mut res, &from_u64(a), &from_u64(m));
+            assert_eq!(res, from_u64(gcd(a, m)), "gcd({}, {})", a, m);
+
+            let mut inv = VecBigNum::<u8>::new_zeroed();
+            let invertible = SimpleEngine::inv_mod_resize(&mut inv, &from_u64(a), &from_u64(m));
+            assert_eq!(invertible, gcd(a % m, m) == 1, "{} ^ -1 mod {}", a, m);
+            if invertible {
+                let mut check = inv.clone();
+                check.resize(8);
+                let check =
+                    u64::from_le_bytes(<[u8; 8]>::try_from(&check.into_inner()[..]).unwrap());
+                assert!(check < m);
+                assert_eq!((check as u128 * a as u128 % m as u128) as u64, 1 % m);
+            }
+        }
+
+        let mut res = VecBigNum::<u8>::new_zeroed();
+        SimpleEngine::gcd_resize(&mut res, &from_u64(0), &from_u64(0));
+        assert!(res.is_zero());
+    }
 }
@@ -0,0 +1,59 @@
+use crate::num::{BigNum, BigNumBits, DynamicBigNum, UnsignedNumDigit, VecBigNum};
+use crate::rsa::RsaError;
+
+/// Converts nonnegative integer to octet string of length `len`(I2OSP from PKCS#1).
+/// Octet string is big endian.
+///
+/// # Errors
+/// Returns `IntegerTooLarge` when integer does not fit `len` bytes.
+pub fn i2osp(x: &impl BigNum, len: usize) -> Result<Vec<u8>, RsaError> {
+    if x.bit_len() > len * 8 {
+        return Err(RsaError::IntegerTooLarge);
+    }
+
+    let mut res = vec![0; len];
+    for (i, byte) in res.iter_mut().rev().enumerate() {
+        for bit in 0..8 {
+            if x.test_bit(i * 8 + bit) {
+                *byte |= 1 << bit;
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Converts big endian octet string to nonnegative integer(OS2IP from PKCS#1).
+pub fn os2ip<D>(bytes: &[u8]) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let digit_bits = D::NUM_BITS as usize;
+    let mut res = VecBigNum::new_zeroed_sized((bytes.len() * 8 + digit_bits - 1) / digit_bits);
+    for (i, &byte) in bytes.iter().rev().enumerate() {
+        // digits are at least 8 bits wide and their size is multiple of 8, so byte never spans two digits
+        let pos = i * 8;
+        res[pos / digit_bits] |= D::try_from(byte).ok().unwrap() << (pos % digit_bits) as u32;
+    }
+    res.normalize();
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_octet_string_conversion() {
+        let bytes = [0x00u8, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xff];
+        let x = os2ip::<u32>(&bytes);
+        assert_eq!(x, VecBigNum::from(vec![0x0607_08ffu32, 0x0203_0405, 0x01]));
+        assert_eq!(i2osp(&x, bytes.len()).unwrap(), bytes);
+        assert_eq!(i2osp(&x, 12).unwrap()[3..], bytes[1..]);
+        assert_eq!(i2osp(&x, 8), Err(RsaError::IntegerTooLarge));
+
+        let x = os2ip::<u8>(&bytes);
+        assert_eq!(i2osp(&x, 9).unwrap(), &bytes[1..]);
+        assert!(os2ip::<u64>(&[]).is_zero());
+        assert_eq!(i2osp(&os2ip::<u64>(&[]), 0).unwrap(), Vec::<u8>::new());
+    }
+}
@@ -0,0 +1,248 @@
+use crate::num::{BigNum, BigNumBits, UnsignedNumDigit, VecBigNum};
+use crate::rsa::{i2osp, os2ip, RsaError, RsaPrivateKey, RsaPublicKey};
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_NULL: u8 = 0x05;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+
+/// Encoded object identifier of rsaEncryption(1.2.840.113549.1.1.1).
+const RSA_ENCRYPTION_OID: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+/// Reader of DER encoded values, which accepts only subset of DER needed by RSA keys.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], RsaError> {
+        if self.data.len() < len {
+            return Err(RsaError::InvalidEncoding);
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn read_byte(&mut self) -> Result<u8, RsaError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_length(&mut self) -> Result<usize, RsaError> {
+        let first = self.read_byte()?;
+        if first < 0x80 {
+            return Ok(first as usize);
+        }
+
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > core::mem::size_of::<usize>() {
+            return Err(RsaError::InvalidEncoding);
+        }
+        let bytes = self.take(count)?;
+        // DER requires the shortest form of length
+        if bytes[0] == 0 {
+            return Err(RsaError::InvalidEncoding);
+        }
+        let len = bytes.iter().fold(0, |acc, &b| (acc << 8) | b as usize);
+        if len < 0x80 {
+            return Err(RsaError::InvalidEncoding);
+        }
+        Ok(len)
+    }
+
+    /// Reads value with given tag and returns its contents.
+    fn read_tlv(&mut self, tag: u8) -> Result<&'a [u8], RsaError> {
+        if self.read_byte()? != tag {
+            return Err(RsaError::InvalidEncoding);
+        }
+        let len = self.read_length()?;
+        self.take(len)
+    }
+
+    /// Reads nonnegative integer in minimal encoding.
+    fn read_integer<D>(&mut self) -> Result<VecBigNum<D>, RsaError>
+    where
+        D: UnsignedNumDigit,
+    {
+        let bytes = self.read_tlv(TAG_INTEGER)?;
+        match bytes {
+            [] => return Err(RsaError::InvalidEncoding),
+            [first, ..] if first & 0x80 != 0 => return Err(RsaError::InvalidEncoding),
+            [0, second, ..] if second & 0x80 == 0 => return Err(RsaError::InvalidEncoding),
+            _ => {}
+        }
+        Ok(os2ip(bytes))
+    }
+
+    /// Checks that all data was read.
+    fn finish(self) -> Result<(), RsaError> {
+        if !self.data.is_empty() {
+            return Err(RsaError::InvalidEncoding);
+        }
+        Ok(())
+    }
+}
+
+fn write_tlv(out: &mut Vec<u8>, tag: u8, contents: &[u8]) {
+    out.push(tag);
+    let len = contents.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let len_bytes = len.to_be_bytes();
+        let skip = len_bytes.iter().take_while(|&&b| b == 0).count();
+        out.push(0x80 | (len_bytes.len() - skip) as u8);
+        out.extend_from_slice(&len_bytes[skip..]);
+    }
+    out.extend_from_slice(contents);
+}
+
+fn write_integer(out: &mut Vec<u8>, x: &impl BigNum) {
+    // extra byte keeps the sign bit clear and it is needed only when bit length is multiple of 8
+    let bytes = i2osp(x, x.bit_len() / 8 + 1).expect("Integer fits its own size");
+    write_tlv(out, TAG_INTEGER, &bytes);
+}
+
+impl<D> RsaPublicKey<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Parses DER encoded PKCS#1 `RSAPublicKey`.
+    ///
+    /// # Errors
+    /// Returns `InvalidEncoding` when data is malformed and `InvalidKey` when components are invalid.
+    pub fn from_pkcs1_der(der: &[u8]) -> Result<Self, RsaError> {
+        let mut outer = Reader::new(der);
+        let mut seq = Reader::new(outer.read_tlv(TAG_SEQUENCE)?);
+        outer.finish()?;
+
+        let n = seq.read_integer()?;
+        let e = seq.read_integer()?;
+        seq.finish()?;
+        Self::new(n, e)
+    }
+
+    /// Encodes key as DER PKCS#1 `RSAPublicKey`.
+    pub fn to_pkcs1_der(&self) -> Vec<u8> {
+        let mut contents = Vec::new();
+        write_integer(&mut contents, self.n());
+        write_integer(&mut contents, self.e());
+
+        let mut out = Vec::new();
+        write_tlv(&mut out, TAG_SEQUENCE, &contents);
+        out
+    }
+
+    /// Parses DER encoded X.509 `SubjectPublicKeyInfo` with rsaEncryption algorithm.
+    ///
+    /// # Errors
+    /// Returns `InvalidEncoding` when data is malformed or algorithm is not RSA and `InvalidKey` when components are invalid.
+    pub fn from_public_key_der(der: &[u8]) -> Result<Self, RsaError> {
+        let mut outer = Reader::new(der);
+        let mut spki = Reader::new(outer.read_tlv(TAG_SEQUENCE)?);
+        outer.finish()?;
+
+        let mut algorithm = Reader::new(spki.read_tlv(TAG_SEQUENCE)?);
+        if algorithm.read_tlv(TAG_OID)? != RSA_ENCRYPTION_OID {
+            return Err(RsaError::InvalidEncoding);
+        }
+        // parameters are NULL, but some encoders omit them
+        if !algorithm.data.is_empty() && !algorithm.read_tlv(TAG_NULL)?.is_empty() {
+            return Err(RsaError::InvalidEncoding);
+        }
+        algorithm.finish()?;
+
+        let bits = spki.read_tlv(TAG_BIT_STRING)?;
+        spki.finish()?;
+        match bits {
+            [0, key @ ..] => Self::from_pkcs1_der(key),
+            _ => Err(RsaError::InvalidEncoding),
+        }
+    }
+
+    /// Encodes key as DER X.509 `SubjectPublicKeyInfo`.
+    pub fn to_public_key_der(&self) -> Vec<u8> {
+        let mut algorithm = Vec::new();
+        write_tlv(&mut algorithm, TAG_OID, RSA_ENCRYPTION_OID);
+        write_tlv(&mut algorithm, TAG_NULL, &[]);
+
+        let mut bits = vec![0];
+        bits.extend_from_slice(&self.to_pkcs1_der());
+
+        let mut contents = Vec::new();
+        write_tlv(&mut contents, TAG_SEQUENCE, &algorithm);
+        write_tlv(&mut contents, TAG_BIT_STRING, &bits);
+
+        let mut out = Vec::new();
+        write_tlv(&mut out, TAG_SEQUENCE, &contents);
+        out
+    }
+}
+
+impl<D> RsaPrivateKey<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Parses DER encoded PKCS#1 `RSAPrivateKey`.
+    /// Only two prime keys(version 0) are supported.
+    ///
+    /// # Errors
+    /// Returns `InvalidEncoding` when data is malformed or version is not supported
+    /// and `InvalidKey` when components are not consistent with each other.
+    pub fn from_pkcs1_der(der: &[u8]) -> Result<Self, RsaError> {
+        let mut outer = Reader::new(der);
+        let mut seq = Reader::new(outer.read_tlv(TAG_SEQUENCE)?);
+        outer.finish()?;
+
+        let version: VecBigNum<D> = seq.read_integer()?;
+        if !version.is_zero() {
+            return Err(RsaError::InvalidEncoding);
+        }
+
+        let n = seq.read_integer()?;
+        let e = seq.read_integer()?;
+        let d = seq.read_integer()?;
+        let p = seq.read_integer()?;
+        let q = seq.read_integer()?;
+        let dp: VecBigNum<D> = seq.read_integer()?;
+        let dq: VecBigNum<D> = seq.read_integer()?;
+        let qinv: VecBigNum<D> = seq.read_integer()?;
+        seq.finish()?;
+
+        // CRT parameters are computed again, so stored ones just have to match them
+        let key = Self::from_components(n, e, d, p, q)?;
+        if &dp != key.dp() || &dq != key.dq() || &qinv != key.qinv() {
+            return Err(RsaError::InvalidKey);
+        }
+        Ok(key)
+    }
+
+    /// Encodes key as DER PKCS#1 `RSAPrivateKey`.
+    pub fn to_pkcs1_der(&self) -> Vec<u8> {
+        let mut contents = Vec::new();
+        write_tlv(&mut contents, TAG_INTEGER, &[0]);
+        for x in [
+            self.n(),
+            self.e(),
+            self.d(),
+            self.p(),
+            self.q(),
+            self.dp(),
+            self.dq(),
+            self.qinv(),
+        ]
+        .iter()
+        {
+            write_integer(&mut contents, *x);
+        }
+
+        let mut out = Vec::new();
+        write_tlv(&mut out, TAG_SEQUENCE, &contents);
+        out
+    }
+}
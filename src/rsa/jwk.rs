@@ -0,0 +1,365 @@
+use crate::num::{BigNumBits, UnsignedNumDigit, VecBigNum};
+use crate::rsa::pem::{base64_decode, base64_encode};
+use crate::rsa::{i2osp, os2ip, RsaError, RsaPrivateKey, RsaPublicKey};
+use std::collections::HashMap;
+
+/// Parser of JSON object, which keeps only values of string members.
+/// Values of other types are validated just enough to skip them.
+struct JsonParser<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), RsaError> {
+        self.skip_whitespace();
+        if self.peek() != Some(c) {
+            return Err(RsaError::InvalidEncoding);
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn parse_string(&mut self) -> Result<String, RsaError> {
+        self.expect(b'"')?;
+        let mut res = Vec::new();
+        loop {
+            let c = self.peek().ok_or(RsaError::InvalidEncoding)?;
+            self.pos += 1;
+            match c {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = self.peek().ok_or(RsaError::InvalidEncoding)?;
+                    self.pos += 1;
+                    let c = match escaped {
+                        b'"' | b'\\' | b'/' => escaped as char,
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let hex = self
+                                .data
+                                .get(self.pos..self.pos + 4)
+                                .and_then(|hex| core::str::from_utf8(hex).ok())
+                                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                                .ok_or(RsaError::InvalidEncoding)?;
+                            self.pos += 4;
+                            // surrogate pairs never appear in key members, so they are rejected
+                            core::char::from_u32(hex).ok_or(RsaError::InvalidEncoding)?
+                        }
+                        _ => return Err(RsaError::InvalidEncoding),
+                    };
+                    let mut buf = [0; 4];
+                    res.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                0x00..=0x1f => return Err(RsaError::InvalidEncoding),
+                _ => res.push(c),
+            }
+        }
+        String::from_utf8(res).map_err(|_| RsaError::InvalidEncoding)
+    }
+
+    /// Skips value of any type, which is not string.
+    fn skip_value(&mut self) -> Result<(), RsaError> {
+        self.skip_whitespace();
+        match self.peek().ok_or(RsaError::InvalidEncoding)? {
+            b'"' => {
+                self.parse_string()?;
+            }
+            open @ (b'{' | b'[') => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.pos += 1;
+                self.skip_whitespace();
+                if self.peek() == Some(close) {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                loop {
+                    if open == b'{' {
+                        self.parse_string()?;
+                        self.expect(b':')?;
+                    }
+                    self.skip_value()?;
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(c) if c == close => {
+                            self.pos += 1;
+                            break;
+                        }
+                        _ => return Err(RsaError::InvalidEncoding),
+                    }
+                }
+            }
+            _ => {
+                // numbers and literals
+                let start = self.pos;
+                while let Some(b'0'..=b'9' | b'a'..=b'z' | b'+' | b'-' | b'.' | b'E') = self.peek()
+                {
+                    self.pos += 1;
+                }
+                if start == self.pos {
+                    return Err(RsaError::InvalidEncoding);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses whole input as single object and returns its members.
+    /// Members, which are not strings, have no value.
+    fn parse_object(data: &'a str) -> Result<HashMap<String, Option<String>>, RsaError> {
+        let mut parser = Self {
+            data: data.as_bytes(),
+            pos: 0,
+        };
+        let mut members = HashMap::new();
+
+        parser.expect(b'{')?;
+        parser.skip_whitespace();
+        if parser.peek() == Some(b'}') {
+            parser.pos += 1;
+        } else {
+            loop {
+                let key = parser.parse_string()?;
+                parser.expect(b':')?;
+                parser.skip_whitespace();
+                let value = if parser.peek() == Some(b'"') {
+                    Some(parser.parse_string()?)
+                } else {
+                    parser.skip_value()?;
+                    None
+                };
+                if members.insert(key, value).is_some() {
+                    return Err(RsaError::InvalidEncoding);
+                }
+
+                parser.skip_whitespace();
+                match parser.peek() {
+                    Some(b',') => parser.pos += 1,
+                    Some(b'}') => {
+                        parser.pos += 1;
+                        break;
+                    }
+                    _ => return Err(RsaError::InvalidEncoding),
+                }
+            }
+        }
+
+        parser.skip_whitespace();
+        if parser.pos != parser.data.len() {
+            return Err(RsaError::InvalidEncoding);
+        }
+        Ok(members)
+    }
+}
+
+/// Returns members of RSA JWK, after checking its key type.
+fn parse_rsa_jwk(jwk: &str) -> Result<HashMap<String, Option<String>>, RsaError> {
+    let members = JsonParser::parse_object(jwk)?;
+    if members.get("kty").and_then(Option::as_deref) != Some("RSA") {
+        return Err(RsaError::InvalidEncoding);
+    }
+    Ok(members)
+}
+
+/// Decodes integer member, which is base64url encoded big endian octet string.
+fn get_integer<D>(
+    members: &HashMap<String, Option<String>>,
+    name: &str,
+) -> Result<VecBigNum<D>, RsaError>
+where
+    D: UnsignedNumDigit,
+{
+    let text = members
+        .get(name)
+        .and_then(Option::as_ref)
+        .ok_or(RsaError::InvalidEncoding)?;
+    let bytes = base64_decode(text, true)?;
+    if bytes.is_empty() {
+        return Err(RsaError::InvalidEncoding);
+    }
+    Ok(os2ip(&bytes))
+}
+
+fn push_member(out: &mut String, name: &str, value: &str) {
+    if out.len() > 1 {
+        out.push(',');
+    }
+    out.push_str(&format!("\"{}\":\"{}\"", name, value));
+}
+
+fn push_integer<D>(out: &mut String, name: &str, x: &VecBigNum<D>)
+where
+    D: UnsignedNumDigit,
+{
+    // zero still takes single octet
+    let len = ((x.bit_len() + 7) / 8).max(1);
+    let bytes = i2osp(x, len).expect("Integer fits its own size");
+    push_member(out, name, &base64_encode(&bytes, true));
+}
+
+impl<D> RsaPublicKey<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Parses RSA JSON web key(RFC 7517). Only `n` and `e` members are used.
+    ///
+    /// # Errors
+    /// Returns `InvalidEncoding` when JSON is malformed, key type is not `RSA` or members are missing
+    /// and `InvalidKey` when components are invalid.
+    pub fn from_jwk(jwk: &str) -> Result<Self, RsaError> {
+        let members = parse_rsa_jwk(jwk)?;
+        Self::new(get_integer(&members, "n")?, get_integer(&members, "e")?)
+    }
+
+    /// Encodes key as JSON web key.
+    pub fn to_jwk(&self) -> String {
+        let mut out = String::from("{");
+        push_member(&mut out, "kty", "RSA");
+        push_integer(&mut out, "n", self.n());
+        push_integer(&mut out, "e", self.e());
+        out.push('}');
+        out
+    }
+}
+
+impl<D> RsaPrivateKey<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Parses RSA private JSON web key(RFC 7517).
+    /// Keys with more than two primes(`oth` member) are not supported.
+    /// CRT members `dp`, `dq` and `qi` are optional, but when present they must match other components.
+    ///
+    /// # Errors
+    /// Returns `InvalidEncoding` when JSON is malformed, key type is not `RSA` or members are missing
+    /// and `InvalidKey` when components are not consistent with each other.
+    pub fn from_jwk(jwk: &str) -> Result<Self, RsaError> {
+        let members = parse_rsa_jwk(jwk)?;
+        if members.contains_key("oth") {
+            return Err(RsaError::InvalidEncoding);
+        }
+
+        let key = Self::from_components(
+            get_integer(&members, "n")?,
+            get_integer(&members, "e")?,
+            get_integer(&members, "d")?,
+            get_integer(&members, "p")?,
+            get_integer(&members, "q")?,
+        )?;
+
+        for &(name, expected) in &[("dp", key.dp()), ("dq", key.dq()), ("qi", key.qinv())] {
+            if members.contains_key(name) && &get_integer::<D>(&members, name)? != expected {
+                return Err(RsaError::InvalidKey);
+            }
+        }
+        Ok(key)
+    }
+
+    /// Encodes key as private JSON web key with all CRT members.
+    pub fn to_jwk(&self) -> String {
+        let mut out = String::from("{");
+        push_member(&mut out, "kty", "RSA");
+        push_integer(&mut out, "n", self.n());
+        push_integer(&mut out, "e", self.e());
+        push_integer(&mut out, "d", self.d());
+        push_integer(&mut out, "p", self.p());
+        push_integer(&mut out, "q", self.q());
+        push_integer(&mut out, "dp", self.dp());
+        push_integer(&mut out, "dq", self.dq());
+        push_integer(&mut out, "qi", self.qinv());
+        out.push('}');
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rsa::pem::test::PRIVATE_KEY_PEM;
+
+    /// The same key as `PRIVATE_KEY_PEM`.
+    const PRIVATE_KEY_JWK: &str = r#"{"kty":"RSA","n":"4Qy85gx3cBvpA6MrkBZcP_HDV4kH2yMsxtxufzno4myaHrsOktglctrWz_0SZQqEBka791H2CFXt5rbOmLH6jw","e":"AQAB","d":"Nsj1C0Opp78DoIEOT8tBtcdS-HJBWVVcxyROOeaNDOFSNwB4zIsLNqHQNgbkRPvQbKWldAT8JC0EQzEpm_ipwQ","p":"_KuJuPFloawYcIUgJvO3KLHCXGglrNRthfY5HT514J8","q":"5AQDmzC950SDMkX_1NHTYd6b5TJjIQlmKyc_5v4X8BE","dp":"1ggM6s_5cmEQdsgeO0C5x0wEwzgpqeRsPk0bhm0xdys","dq":"dbjM09b6dOieg9o4F3ytkh4IhvDY1TmxEh6Vk4EkgsE","qi":"EMbarxLO1wtnchJfo6X9M06Q73rl8Iy61k5MTwYmXtk"}"#;
+
+    #[test]
+    fn test_jwk_round_trip() {
+        let key = RsaPrivateKey::<u32>::from_jwk(PRIVATE_KEY_JWK).unwrap();
+        assert_eq!(key.to_jwk(), PRIVATE_KEY_JWK);
+        assert_eq!(
+            key.to_pkcs1_der(),
+            RsaPrivateKey::<u32>::from_pkcs1_pem(PRIVATE_KEY_PEM)
+                .unwrap()
+                .to_pkcs1_der()
+        );
+
+        let public = RsaPublicKey::<u64>::from_jwk(PRIVATE_KEY_JWK).unwrap();
+        assert_eq!(public.n().bit_len(), 512);
+        assert_eq!(
+            public.to_jwk(),
+            r#"{"kty":"RSA","n":"4Qy85gx3cBvpA6MrkBZcP_HDV4kH2yMsxtxufzno4myaHrsOktglctrWz_0SZQqEBka791H2CFXt5rbOmLH6jw","e":"AQAB"}"#
+        );
+        assert_eq!(
+            RsaPublicKey::<u8>::from_jwk(&public.to_jwk())
+                .unwrap()
+                .to_jwk(),
+            public.to_jwk()
+        );
+    }
+
+    #[test]
+    fn test_jwk_other_members() {
+        let jwk = r#" {
+            "kid" : "key\"1A",
+            "use": "sig",
+            "key_ops": ["verify", {"nested": [1, 2.5e3, true, null]}],
+            "ext": false,
+            "kty": "RSA",
+            "n": "4Qy85gx3cBvpA6MrkBZcP_HDV4kH2yMsxtxufzno4myaHrsOktglctrWz_0SZQqEBka791H2CFXt5rbOmLH6jw",
+            "e": "AQAB"
+        } "#;
+        let public = RsaPublicKey::<u32>::from_jwk(jwk).unwrap();
+        assert_eq!(public.e(), &VecBigNum::from(vec![65537u32]));
+    }
+
+    #[test]
+    fn test_invalid_jwk() {
+        let invalid = [
+            r#"{"kty":"EC","n":"AQAB","e":"AQAB"}"#,
+            r#"{"kty":"RSA","e":"AQAB"}"#,
+            r#"{"kty":"RSA","n":"+/8","e":"AQAB"}"#,
+            r#"{"kty":"RSA","n":"","e":"AQAB"}"#,
+            r#"{"kty":"RSA","kty":"RSA"}"#,
+            r#"{"kty":"RSA",}"#,
+            r#"{"kty":"RSA"} x"#,
+            r#"["kty"]"#,
+        ];
+        for jwk in invalid.iter() {
+            assert_eq!(
+                RsaPublicKey::<u32>::from_jwk(jwk).unwrap_err(),
+                RsaError::InvalidEncoding,
+                "{}",
+                jwk
+            );
+        }
+
+        // qi does not match other components
+        let jwk = PRIVATE_KEY_JWK.replace("\"qi\":\"E", "\"qi\":\"F");
+        assert_eq!(
+            RsaPrivateKey::<u32>::from_jwk(&jwk).err(),
+            Some(RsaError::InvalidKey)
+        );
+    }
+}
@@ -0,0 +1,605 @@
+use crate::iutil::from_u64;
+use crate::num::{
+    BigNum, BigNumBits, DefaultBigNumDigit, DynamicBigNum, UnsignedNumDigit, VecBigNum,
+};
+use crate::ops::{SimpleEngine, UnsignedEngine};
+use crate::rsa::RsaError;
+use core::cmp::Ordering;
+#[cfg(feature = "rand")]
+use {
+    crate::num::{generate_prime, BigNumRandom},
+    rand_core::RngCore,
+};
+
+/// Public exponent used for generated keys.
+pub const DEFAULT_PUBLIC_EXPONENT: u64 = 65537;
+
+fn mod_reduce<D>(value: &VecBigNum<D>, modulus: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut quot = VecBigNum::new_zeroed();
+    let mut rem = VecBigNum::new_zeroed();
+    SimpleEngine::div_rem_resize(&mut quot, &mut rem, value, modulus);
+    rem
+}
+
+fn mod_mul<D>(lhs: &VecBigNum<D>, rhs: &VecBigNum<D>, modulus: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut prod = VecBigNum::new_zeroed();
+    SimpleEngine::mul_resize(&mut prod, lhs, rhs);
+    mod_reduce(&prod, modulus)
+}
+
+fn pow_mod<D>(base: &VecBigNum<D>, exp: &VecBigNum<D>, modulus: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = VecBigNum::new_zeroed();
+    SimpleEngine::pow_mod_resize(&mut res, base, exp, modulus);
+    res
+}
+
+/// Width of windows used by `pow_mod_fixed_window`.
+const WINDOW_BITS: usize = 4;
+
+/// Computes `base^exp mod modulus`, where `exp` has at most `exp_bits` bits, using fixed window exponentiation.
+///
+/// Unlike in `pow_mod` sequence of operations depends only on `exp_bits`: every window takes the same amount of
+/// squarings followed by one multiplication, even if it's zero, and entry of precomputed table is selected
+/// by reading all of them. So neither branches nor memory accesses depend on bits of `exp`.
+/// Note that underlying multiplication and division are not constant time.
+fn pow_mod_fixed_window<D>(
+    base: &VecBigNum<D>,
+    exp: &VecBigNum<D>,
+    modulus: &VecBigNum<D>,
+    exp_bits: usize,
+) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let size = modulus.significant_len();
+    let padded = |mut x: VecBigNum<D>| {
+        x.resize(size);
+        x
+    };
+
+    // table[i] = base^i mod modulus
+    let base = mod_reduce(base, modulus);
+    let mut table = Vec::with_capacity(1 << WINDOW_BITS);
+    table.push(padded(mod_reduce(&from_u64(1), modulus)));
+    for i in 1..(1 << WINDOW_BITS) {
+        let entry = mod_mul(&table[i - 1], &base, modulus);
+        table.push(padded(entry));
+    }
+
+    let mut acc = table[0].clone();
+    let windows = (exp_bits + WINDOW_BITS - 1) / WINDOW_BITS;
+    for w in (0..windows).rev() {
+        for _ in 0..WINDOW_BITS {
+            acc = mod_mul(&acc, &acc, modulus);
+        }
+
+        let mut index = 0;
+        for bit in 0..WINDOW_BITS {
+            index |= (exp.test_bit(w * WINDOW_BITS + bit) as usize) << bit;
+        }
+
+        let mut selected = VecBigNum::new_zeroed_sized(size);
+        for (i, entry) in table.iter().enumerate() {
+            // mask has all bits set only for the entry at index
+            let diff = (i ^ index) as u64;
+            let equal = (((diff | diff.wrapping_neg()) >> 63) ^ 1) as u8;
+            let mask = D::ZERO.wrapping_sub(D::try_from(equal).ok().unwrap());
+            for k in 0..size {
+                selected[k] |= entry[k] & mask;
+            }
+        }
+        acc = mod_mul(&acc, &selected, modulus);
+    }
+    acc
+}
+
+fn minus_one<D>(value: &VecBigNum<D>) -> VecBigNum<D>
+where
+    D: UnsignedNumDigit,
+{
+    let mut res = value.clone();
+    SimpleEngine::sub_resize(&mut res, &from_u64(1));
+    res
+}
+
+/// RSA public key.
+#[derive(Debug, Clone)]
+pub struct RsaPublicKey<D = DefaultBigNumDigit> {
+    n: VecBigNum<D>,
+    e: VecBigNum<D>,
+}
+
+impl<D> RsaPublicKey<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Creates public key from modulus `n` and public exponent `e`.
+    ///
+    /// # Errors
+    /// Returns `InvalidKey` when modulus is not odd number bigger than one or exponent is not in range `[3, n)`.
+    pub fn new(n: VecBigNum<D>, e: VecBigNum<D>) -> Result<Self, RsaError> {
+        if SimpleEngine::compare_u64(&n, 3) == Ordering::Less
+            || n[0] & D::ONE == D::ZERO
+            || SimpleEngine::compare_u64(&e, 3) == Ordering::Less
+            || SimpleEngine::compare(&e, &n) != Ordering::Less
+        {
+            return Err(RsaError::InvalidKey);
+        }
+        Ok(Self { n, e })
+    }
+
+    /// Returns modulus.
+    pub fn n(&self) -> &VecBigNum<D> {
+        &self.n
+    }
+
+    /// Returns public exponent.
+    pub fn e(&self) -> &VecBigNum<D> {
+        &self.e
+    }
+
+    /// Returns size of modulus in bytes, which is size of ciphertexts and signatures.
+    pub fn size(&self) -> usize {
+        (self.n.bit_len() + 7) / 8
+    }
+
+    /// Encrypts message representative `m`(RSAEP).
+    ///
+    /// # Errors
+    /// Returns `RepresentativeOutOfRange` when `m` is not smaller than modulus.
+    pub fn encrypt(&self, m: &VecBigNum<D>) -> Result<VecBigNum<D>, RsaError> {
+        self.check_range(m)?;
+        Ok(pow_mod(m, &self.e, &self.n))
+    }
+
+    /// Recovers message representative from signature representative `s`(RSAVP1).
+    ///
+    /// # Errors
+    /// Returns `RepresentativeOutOfRange` when `s` is not smaller than modulus.
+    pub fn verify(&self, s: &VecBigNum<D>) -> Result<VecBigNum<D>, RsaError> {
+        self.check_range(s)?;
+        Ok(pow_mod(s, &self.e, &self.n))
+    }
+
+    fn check_range(&self, x: &VecBigNum<D>) -> Result<(), RsaError> {
+        if SimpleEngine::compare(x, &self.n) != Ordering::Less {
+            return Err(RsaError::RepresentativeOutOfRange);
+        }
+        Ok(())
+    }
+}
+
+/// RSA private key with parameters used by Chinese remainder theorem.
+///
+/// Private exponentiation uses fixed window method, so its sequence of operations does not depend
+/// on bits of private exponents, and ciphertexts and messages are blinded before exponentiation.
+/// However, underlying bignum multiplication and division are not constant time,
+/// so these are only mitigations and timing of private operations is not guaranteed to be independent of secrets.
+#[derive(Clone)]
+pub struct RsaPrivateKey<D = DefaultBigNumDigit> {
+    public: RsaPublicKey<D>,
+    d: VecBigNum<D>,
+    p: VecBigNum<D>,
+    q: VecBigNum<D>,
+    dp: VecBigNum<D>,
+    dq: VecBigNum<D>,
+    qinv: VecBigNum<D>,
+}
+
+impl<D> RsaPrivateKey<D>
+where
+    D: UnsignedNumDigit,
+{
+    /// Creates private key from modulus, exponents and primes.
+    /// Parameters of Chinese remainder theorem are computed.
+    ///
+    /// # Errors
+    /// Returns `InvalidKey` when `n` is not `p * q` or exponents do not match each other.
+    pub fn from_components(
+        n: VecBigNum<D>,
+        e: VecBigNum<D>,
+        d: VecBigNum<D>,
+        p: VecBigNum<D>,
+        q: VecBigNum<D>,
+    ) -> Result<Self, RsaError> {
+        let public = RsaPublicKey::new(n, e)?;
+
+        let mut n = VecBigNum::new_zeroed();
+        SimpleEngine::mul_resize(&mut n, &p, &q);
+        if n != public.n || p.is_one() || q.is_one() {
+            return Err(RsaError::InvalidKey);
+        }
+
+        // e * d = 1 modulo both p - 1 and q - 1
+        let p_minus_one = minus_one(&p);
+        let q_minus_one = minus_one(&q);
+        let dp = mod_reduce(&d, &p_minus_one);
+        let dq = mod_reduce(&d, &q_minus_one);
+        if !mod_mul(&public.e, &dp, &p_minus_one).is_one()
+            || !mod_mul(&public.e, &dq, &q_minus_one).is_one()
+        {
+            return Err(RsaError::InvalidKey);
+        }
+
+        let mut qinv = VecBigNum::new_zeroed();
+        if !SimpleEngine::inv_mod_resize(&mut qinv, &q, &p) {
+            return Err(RsaError::InvalidKey);
+        }
+
+        Ok(Self {
+            public,
+            d,
+            p,
+            q,
+            dp,
+            dq,
+            qinv,
+        })
+    }
+
+    /// Generates new key, which modulus has exactly `bits` bits.
+    /// Public exponent is `DEFAULT_PUBLIC_EXPONENT`.
+    ///
+    /// # Panic
+    /// Panics when `bits` is smaller than 64.
+    #[cfg(feature = "rand")]
+    pub fn generate<R>(bits: usize, rng: &mut R) -> Self
+    where
+        R: RngCore + ?Sized,
+    {
+        assert!(bits >= 64, "Modulus is too small");
+        let e = from_u64(DEFAULT_PUBLIC_EXPONENT);
+
+        loop {
+            let p: VecBigNum<D> = generate_prime((bits + 1) / 2, rng);
+            let q: VecBigNum<D> = generate_prime(bits / 2, rng);
+            if p == q {
+                continue;
+            }
+
+            let mut n = VecBigNum::new_zeroed();
+            SimpleEngine::mul_resize(&mut n, &p, &q);
+            if n.bit_len() != bits {
+                continue;
+            }
+
+            // d = e^-1 mod lcm(p - 1, q - 1)
+            let p_minus_one = minus_one(&p);
+            let q_minus_one = minus_one(&q);
+            let mut gcd = VecBigNum::new_zeroed();
+            SimpleEngine::gcd_resize(&mut gcd, &p_minus_one, &q_minus_one);
+            let mut lcm = VecBigNum::new_zeroed();
+            let mut rem = VecBigNum::new_zeroed();
+            SimpleEngine::div_rem_resize(&mut lcm, &mut rem, &p_minus_one, &gcd);
+            let lcm = {
+                let mut res = VecBigNum::new_zeroed();
+                SimpleEngine::mul_resize(&mut res, &lcm, &q_minus_one);
+                res
+            };
+
+            let mut d = VecBigNum::new_zeroed();
+            if !SimpleEngine::inv_mod_resize(&mut d, &e, &lcm) {
+                continue;
+            }
+
+            return Self::from_components(n, e, d, p, q)
+                .expect("Generated components are consistent");
+        }
+    }
+
+    /// Returns public part of this key.
+    pub fn to_public_key(&self) -> RsaPublicKey<D> {
+        self.public.clone()
+    }
+
+    /// Returns modulus.
+    pub fn n(&self) -> &VecBigNum<D> {
+        &self.public.n
+    }
+
+    /// Returns public exponent.
+    pub fn e(&self) -> &VecBigNum<D> {
+        &self.public.e
+    }
+
+    /// Returns private exponent.
+    pub fn d(&self) -> &VecBigNum<D> {
+        &self.d
+    }
+
+    /// Returns first prime factor of modulus.
+    pub fn p(&self) -> &VecBigNum<D> {
+        &self.p
+    }
+
+    /// Returns second prime factor of modulus.
+    pub fn q(&self) -> &VecBigNum<D> {
+        &self.q
+    }
+
+    /// Returns `d mod (p - 1)`.
+    pub fn dp(&self) -> &VecBigNum<D> {
+        &self.dp
+    }
+
+    /// Returns `d mod (q - 1)`.
+    pub fn dq(&self) -> &VecBigNum<D> {
+        &self.dq
+    }
+
+    /// Returns `q^-1 mod p`.
+    pub fn qinv(&self) -> &VecBigNum<D> {
+        &self.qinv
+    }
+
+    /// Computes `c^d mod n` using Chinese remainder theorem.
+    ///
+    /// Result is verified with public exponent, since fault in computation of either half
+    /// would reveal factor of modulus as `gcd(m^e - c, n)`.
+    fn crt(&self, c: &VecBigNum<D>) -> Result<VecBigNum<D>, RsaError> {
+        // dp < p and dq < q, so size of primes bounds amount of bits of exponents without revealing them
+        let m1 = pow_mod_fixed_window(c, &self.dp, &self.p, self.p.bit_len());
+        let m2 = pow_mod_fixed_window(c, &self.dq, &self.q, self.q.bit_len());
+
+        // h = qinv * (m1 - m2) mod p
+        let m2p = mod_reduce(&m2, &self.p);
+        let mut diff = m1;
+        if SimpleEngine::compare(&diff, &m2p) == Ordering::Less {
+            SimpleEngine::add_resize(&mut diff, &self.p);
+        }
+        SimpleEngine::sub_resize(&mut diff, &m2p);
+        let h = mod_mul(&self.qinv, &diff, &self.p);
+
+        // m = m2 + h * q
+        let mut m = m2;
+        SimpleEngine::mul_resize(&mut m, &h, &self.q);
+
+        if pow_mod(&m, &self.public.e, &self.public.n) != *c {
+            return Err(RsaError::FaultDetected);
+        }
+        Ok(m)
+    }
+
+    /// Computes `c^d mod n` with blinding factor `r`.
+    fn blinded_crt(&self, c: &VecBigNum<D>, r: &VecBigNum<D>) -> Result<VecBigNum<D>, RsaError> {
+        let n = &self.public.n;
+        let mut r_inv = VecBigNum::new_zeroed();
+        if r.is_zero()
+            || SimpleEngine::compare(r, n) != Ordering::Less
+            || !SimpleEngine::inv_mod_resize(&mut r_inv, r, n)
+        {
+            return Err(RsaError::InvalidBlindingFactor);
+        }
+
+        // (c * r^e)^d = c^d * r
+        let blinded = mod_mul(c, &pow_mod(r, &self.public.e, n), n);
+        Ok(mod_mul(&self.crt(&blinded)?, &r_inv, n))
+    }
+
+    /// Computes `c^d mod n` with random blinding factor.
+    #[cfg(feature = "rand")]
+    fn random_blinded_crt<R>(&self, c: &VecBigNum<D>, rng: &mut R) -> Result<VecBigNum<D>, RsaError>
+    where
+        R: RngCore + ?Sized,
+    {
+        let one = from_u64(1);
+        loop {
+            let r = VecBigNum::random_range(&one, &self.public.n, rng);
+            match self.blinded_crt(c, &r) {
+                Err(RsaError::InvalidBlindingFactor) => continue,
+                res => return res,
+            }
+        }
+    }
+
+    /// Decrypts ciphertext representative `c`(RSADP).
+    /// Chinese remainder theorem and blinding are used.
+    ///
+    /// # Errors
+    /// Returns `RepresentativeOutOfRange` when `c` is not smaller than modulus
+    /// and `FaultDetected` when verification of result fails.
+    #[cfg(feature = "rand")]
+    pub fn decrypt<R>(&self, c: &VecBigNum<D>, rng: &mut R) -> Result<VecBigNum<D>, RsaError>
+    where
+        R: RngCore + ?Sized,
+    {
+        self.public.check_range(c)?;
+        self.random_blinded_crt(c, rng)
+    }
+
+    /// Signs message representative `m`(RSASP1).
+    /// Chinese remainder theorem and blinding are used.
+    ///
+    /// # Errors
+    /// Returns `RepresentativeOutOfRange` when `m` is not smaller than modulus
+    /// and `FaultDetected` when verification of result fails.
+    #[cfg(feature = "rand")]
+    pub fn sign<R>(&self, m: &VecBigNum<D>, rng: &mut R) -> Result<VecBigNum<D>, RsaError>
+    where
+        R: RngCore + ?Sized,
+    {
+        self.public.check_range(m)?;
+        self.random_blinded_crt(m, rng)
+    }
+
+    /// Decrypts ciphertext representative `c`(RSADP) with blinding factor `r` supplied by caller.
+    /// Works just like `decrypt`, which is available with `rand` feature.
+    ///
+    /// `r` should be chosen uniformly at random from range `[1, n)` for every operation,
+    /// otherwise blinding does not hide anything.
+    ///
+    /// # Errors
+    /// Returns `RepresentativeOutOfRange` when `c` is not smaller than modulus
+    /// and `InvalidBlindingFactor` when `r` is not in range `[1, n)` or has no inverse modulo `n`.
+    /// Returns `FaultDetected` when verification of result fails.
+    pub fn decrypt_with_blinding(
+        &self,
+        c: &VecBigNum<D>,
+        r: &VecBigNum<D>,
+    ) -> Result<VecBigNum<D>, RsaError> {
+        self.public.check_range(c)?;
+        self.blinded_crt(c, r)
+    }
+
+    /// Signs message representative `m`(RSASP1) with blinding factor `r` supplied by caller.
+    /// Works just like `sign`, which is available with `rand` feature.
+    ///
+    /// `r` should be chosen uniformly at random from range `[1, n)` for every operation,
+    /// otherwise blinding does not hide anything.
+    ///
+    /// # Errors
+    /// Returns `RepresentativeOutOfRange` when `m` is not smaller than modulus
+    /// and `InvalidBlindingFactor` when `r` is not in range `[1, n)` or has no inverse modulo `n`.
+    /// Returns `FaultDetected` when verification of result fails.
+    pub fn sign_with_blinding(
+        &self,
+        m: &VecBigNum<D>,
+        r: &VecBigNum<D>,
+    ) -> Result<VecBigNum<D>, RsaError> {
+        self.public.check_range(m)?;
+        self.blinded_crt(m, r)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn textbook_key() -> RsaPrivateKey<u8> {
+        RsaPrivateKey::from_components(
+            from_u64(3233),
+            from_u64(17),
+            from_u64(2753),
+            from_u64(61),
+            from_u64(53),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_textbook_key() {
+        let key = textbook_key();
+        assert_eq!(key.dp(), &from_u64(53));
+        assert_eq!(key.dq(), &from_u64(49));
+        assert_eq!(key.qinv(), &from_u64(38));
+
+        let public = key.to_public_key();
+        assert_eq!(public.encrypt(&from_u64(65)).unwrap(), from_u64(2790));
+        assert_eq!(key.crt(&from_u64(2790)), Ok(from_u64(65)));
+        assert_eq!(
+            public.encrypt(&from_u64(3233)),
+            Err(RsaError::RepresentativeOutOfRange)
+        );
+        assert_eq!(public.size(), 2);
+    }
+
+    #[test]
+    fn test_private_operations_with_blinding() {
+        let key = textbook_key();
+        for &r in &[1u64, 2, 1000, 3232] {
+            assert_eq!(
+                key.decrypt_with_blinding(&from_u64(2790), &from_u64(r)),
+                Ok(from_u64(65))
+            );
+            let s = key.sign_with_blinding(&from_u64(65), &from_u64(r)).unwrap();
+            assert_eq!(key.to_public_key().verify(&s), Ok(from_u64(65)));
+        }
+
+        // zero, out of range and multiples of p or q
+        for &r in &[0u64, 3233, 3234, 61, 106] {
+            assert_eq!(
+                key.decrypt_with_blinding(&from_u64(2790), &from_u64(r)),
+                Err(RsaError::InvalidBlindingFactor)
+            );
+        }
+        assert_eq!(
+            key.sign_with_blinding(&from_u64(3233), &from_u64(2)),
+            Err(RsaError::RepresentativeOutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_pow_mod_fixed_window() {
+        let modulus = from_u64::<u8>(1_000_003);
+        for &(b, e) in &[
+            (2u64, 0u64),
+            (2, 1),
+            (3, 1_000_002),
+            (123_456, 65537),
+            (999, 0xffff),
+        ] {
+            assert_eq!(
+                pow_mod_fixed_window(&from_u64(b), &from_u64(e), &modulus, 20),
+                pow_mod(&from_u64(b), &from_u64(e), &modulus),
+                "{} ^ {}",
+                b,
+                e
+            );
+        }
+        assert!(pow_mod_fixed_window::<u8>(&from_u64(5), &from_u64(3), &from_u64(1), 4).is_zero());
+    }
+
+    #[test]
+    fn test_crt_fault() {
+        let mut key = textbook_key();
+        key.dq = from_u64(48);
+        assert_eq!(key.crt(&from_u64(2790)), Err(RsaError::FaultDetected));
+        assert_eq!(
+            key.decrypt_with_blinding(&from_u64(2790), &from_u64(2)),
+            Err(RsaError::FaultDetected)
+        );
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        let n = || from_u64(3233);
+        let e = || from_u64(17);
+        assert!(RsaPrivateKey::<u8>::from_components(
+            n(),
+            e(),
+            from_u64(2753),
+            from_u64(61),
+            from_u64(59)
+        )
+        .is_err());
+        assert!(RsaPrivateKey::<u8>::from_components(
+            n(),
+            e(),
+            from_u64(2754),
+            from_u64(61),
+            from_u64(53)
+        )
+        .is_err());
+        assert!(RsaPublicKey::<u8>::new(from_u64(3234), e()).is_err());
+        assert!(RsaPublicKey::<u8>::new(n(), from_u64(1)).is_err());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_generated_key() {
+        use crate::num::random::test::SplitMix;
+
+        let mut rng = SplitMix(1234);
+        let key = RsaPrivateKey::<u32>::generate(512, &mut rng);
+        assert_eq!(key.n().bit_len(), 512);
+
+        let public = key.to_public_key();
+        for _ in 0..5 {
+            let m = VecBigNum::random_below(key.n(), &mut rng);
+            let c = public.encrypt(&m).unwrap();
+            assert_eq!(key.decrypt(&c, &mut rng).unwrap(), m);
+
+            let s = key.sign(&m, &mut rng).unwrap();
+            assert_eq!(public.verify(&s).unwrap(), m);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Raw RSA primitives as described in PKCS#1.
+//! There is no padding here; it's up to caller to apply one before using these primitives.
+
+use core::fmt;
+
+mod convert;
+pub use self::convert::*;
+
+mod key;
+pub use self::key::*;
+
+// these modules only add import and export methods to keys
+mod der;
+mod jwk;
+mod pem;
+
+/// Error, which may occur during RSA operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsaError {
+    /// Integer is too large to be encoded with requested amount of bytes.
+    IntegerTooLarge,
+    /// Message, ciphertext or signature representative is not smaller than modulus.
+    RepresentativeOutOfRange,
+    /// Key components are not consistent with each other.
+    InvalidKey,
+    /// Encoded key is malformed or has unsupported format.
+    InvalidEncoding,
+    /// Blinding factor is not in range `[1, n)` or has no inverse modulo `n`.
+    InvalidBlindingFactor,
+    /// Result of private operation failed verification, so computation was faulty and result was discarded.
+    FaultDetected,
+}
+
+impl fmt::Display for RsaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RsaError::IntegerTooLarge => write!(f, "integer too large"),
+            RsaError::RepresentativeOutOfRange => write!(f, "representative out of range"),
+            RsaError::InvalidKey => write!(f, "invalid key"),
+            RsaError::InvalidEncoding => write!(f, "invalid encoding"),
+            RsaError::InvalidBlindingFactor => write!(f, "invalid blinding factor"),
+            RsaError::FaultDetected => write!(f, "fault detected"),
+        }
+    }
+}
+
+impl std::error::Error for RsaError {}